@@ -0,0 +1,105 @@
+// How long the grid takes to lay out its blocks, and how many moves a second the snake makes, at a
+// few sizes of grid and lengths of snake. No window needed.
+#include "snek.hpp"
+
+#include <algorithm>
+#include <chrono>
+#include <cstddef>
+#include <iostream>
+#include <utility>
+
+namespace {
+  using Clock = std::chrono::steady_clock;
+
+  double seconds_since(Clock::time_point start) {
+    return std::chrono::duration<double>(Clock::now() - start).count();
+  }
+
+  // Big enough for every block to be laid out.
+  sf::Vector2u resolution(std::size_t horizontal, std::size_t vertical) {
+    return sf::Vector2u(unsigned(float(horizontal) * block_len + 2.0f * grid_origin.x),
+                        unsigned(float(vertical) * block_len + 2.0f * grid_origin.y));
+  }
+
+  // Round the edge of the grid clockwise, turning at each corner.
+  Direction around_the_edge(const Snake &snake, const Grid &grid) {
+    const auto next = snake.next_head();
+
+    if (next.x >= 0 && next.y >= 0 && std::size_t(next.x) < grid.horizontal() &&
+        std::size_t(next.y) < grid.vertical())
+      return snake.direction();
+
+    switch (snake.direction()) {
+    case Direction::Right:
+      return Direction::Down;
+    case Direction::Down:
+      return Direction::Left;
+    case Direction::Left:
+      return Direction::Up;
+    default:
+      return Direction::Right;
+    }
+  }
+
+  void lay_out(std::size_t horizontal, std::size_t vertical) {
+    static constexpr std::size_t rounds = 20;
+
+    const auto start = Clock::now();
+
+    for (std::size_t i = 0; i < rounds; i++) {
+      Grid grid(horizontal, vertical, grid_origin, resolution(horizontal, vertical));
+    }
+
+    std::cout << "grid " << horizontal << "x" << vertical << ": "
+              << seconds_since(start) / rounds * 1000.0 << " ms to lay out\n";
+  }
+
+  void moves(std::size_t horizontal, std::size_t vertical, std::size_t length) {
+    // Every move shifts the whole body along, so longer snakes get fewer of them.
+    static constexpr std::size_t segment_moves = 20000000;
+    const auto count = std::max(segment_moves / length, std::size_t(1000));
+
+    Config config;
+    config.horizontal = horizontal;
+    config.vertical = vertical;
+
+    Grid grid(horizontal, vertical, grid_origin, resolution(horizontal, vertical));
+    Snake snake(grid, config);
+    snake.relocate(sf::Vector2u(0, 0));
+    snake.set_direction(Direction::Right);
+
+    // Grows by eating its way along the edge first.
+    while (snake.len() < length) {
+      snake.set_direction(around_the_edge(snake, grid));
+      grid.place_fruit(grid.to_index(sf::Vector2u(snake.next_head())));
+      snake.move();
+    }
+
+    const auto start = Clock::now();
+
+    for (std::size_t i = 0; i < count; i++) {
+      snake.set_direction(around_the_edge(snake, grid));
+      snake.move();
+    }
+
+    std::cout << "moves on " << horizontal << "x" << vertical << ", length " << length << ": "
+              << double(count) / seconds_since(start) << " a second\n";
+  }
+} // namespace
+
+int main() {
+  const std::pair<std::size_t, std::size_t> sizes[] = {{20, 15}, {100, 100}, {400, 400}};
+
+  for (const auto &[horizontal, vertical] : sizes) {
+    lay_out(horizontal, vertical);
+  }
+
+  for (const auto &[horizontal, vertical] : sizes) {
+    // Anything up to all the way round the edge but for one block.
+    const auto edge = 2 * (horizontal + vertical) - 4;
+
+    for (const auto length : {std::size_t(4), edge / 2, edge - 1}) {
+      moves(horizontal, vertical, length);
+    }
+  }
+}
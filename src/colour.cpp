@@ -0,0 +1,116 @@
+#include "colour.hpp"
+
+#include <SFML/Graphics.hpp>
+#include <algorithm>
+#include <cctype>
+#include <cmath>
+#include <cstdint>
+#include <stdexcept>
+#include <string>
+
+namespace colour {
+  sf::Color from_hex(const std::string &hex) {
+    auto digits = hex;
+    if (!digits.empty() && digits.front() == '#')
+      digits.erase(digits.begin());
+
+    const auto valid = std::all_of(digits.begin(), digits.end(),
+                                   [](unsigned char c) { return std::isxdigit(c) != 0; });
+
+    if (!valid || (digits.size() != 3 && digits.size() != 6 && digits.size() != 8))
+      throw std::invalid_argument("expected a colour like #rrggbb, got \"" + hex + "\"");
+
+    // `#abc` is short for `#aabbcc`.
+    if (digits.size() == 3)
+      digits = {digits[0], digits[0], digits[1], digits[1], digits[2], digits[2]};
+
+    const auto channel = [&](std::size_t i) {
+      return std::uint8_t(std::stoul(digits.substr(i * 2, 2), nullptr, 16));
+    };
+
+    return sf::Color(channel(0), channel(1), channel(2), digits.size() == 8 ? channel(3) : 0xFF);
+  }
+
+  std::string to_hex(sf::Color colour) {
+    static constexpr char digits[] = "0123456789abcdef";
+
+    std::string hex = "#";
+    const auto push = [&](std::uint8_t channel) {
+      hex += digits[channel >> 4];
+      hex += digits[channel & 0xF];
+    };
+
+    push(colour.r);
+    push(colour.g);
+    push(colour.b);
+    if (colour.a != 0xFF)
+      push(colour.a);
+
+    return hex;
+  }
+
+  sf::Color from_hsv(float hue, float saturation, float value) {
+    hue = std::fmod(hue, 360.0f);
+    if (hue < 0.0f)
+      hue += 360.0f;
+
+    const auto chroma = value * saturation;
+    const auto x = chroma * (1.0f - std::abs(std::fmod(hue / 60.0f, 2.0f) - 1.0f));
+    const auto m = value - chroma;
+
+    float r = 0.0f, g = 0.0f, b = 0.0f;
+
+    switch (int(hue / 60.0f)) {
+    case 0:
+      r = chroma, g = x;
+      break;
+    case 1:
+      r = x, g = chroma;
+      break;
+    case 2:
+      g = chroma, b = x;
+      break;
+    case 3:
+      g = x, b = chroma;
+      break;
+    case 4:
+      r = x, b = chroma;
+      break;
+    default:
+      r = chroma, b = x;
+      break;
+    }
+
+    const auto channel = [&](float c) { return std::uint8_t(std::lround((c + m) * 255.0f)); };
+
+    return sf::Color(channel(r), channel(g), channel(b));
+  }
+
+  sf::Color lerp(sf::Color a, sf::Color b, float t) {
+    t = std::clamp(t, 0.0f, 1.0f);
+
+    const auto channel = [&](std::uint8_t from, std::uint8_t to) {
+      return std::uint8_t(std::lround(float(from) + (float(to) - float(from)) * t));
+    };
+
+    return sf::Color(channel(a.r, b.r), channel(a.g, b.g), channel(a.b, b.b), channel(a.a, b.a));
+  }
+
+  float luminance(sf::Color colour) {
+    // Undoes sRGB's gamma first, since the weights are for linear light.
+    const auto linear = [](std::uint8_t channel) {
+      const auto c = float(channel) / 255.0f;
+
+      return c <= 0.04045f ? c / 12.92f : std::pow((c + 0.055f) / 1.055f, 2.4f);
+    };
+
+    return 0.2126f * linear(colour.r) + 0.7152f * linear(colour.g) + 0.0722f * linear(colour.b);
+  }
+
+  sf::Color contrasting(sf::Color background) {
+    // WCAG's contrast ratios against white and against black.
+    const auto light = luminance(background) + 0.05f;
+
+    return 1.05f / light >= light / 0.05f ? sf::Color::White : sf::Color::Black;
+  }
+} // namespace colour
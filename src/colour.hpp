@@ -0,0 +1,25 @@
+#pragma once
+
+#include <SFML/Graphics.hpp>
+#include <string>
+
+namespace colour {
+  // Reads `#rgb`, `#rrggbb` or `#rrggbbaa`, with or without the `#`. Throws
+  // `std::invalid_argument` for anything else.
+  sf::Color from_hex(const std::string &hex);
+
+  // `#rrggbb`, or `#rrggbbaa` unless the colour is opaque, in lowercase.
+  std::string to_hex(sf::Color colour);
+
+  // `hue` in degrees, wrapping around outside of [0, 360); `saturation` and `value` from 0 to 1.
+  sf::Color from_hsv(float hue, float saturation, float value);
+
+  // `a` at 0, `b` at 1 and a straight line in between, alpha included. `t` is clamped to [0, 1].
+  sf::Color lerp(sf::Color a, sf::Color b, float t);
+
+  // Relative luminance from 0 for black to 1 for white, ignoring alpha.
+  float luminance(sf::Color colour);
+
+  // Black or white, whichever has the higher contrast ratio against `background`.
+  sf::Color contrasting(sf::Color background);
+} // namespace colour
@@ -0,0 +1,249 @@
+#pragma once
+
+#include "grid.hpp"
+
+#include <SFML/Graphics.hpp>
+#include <SFML/System/Vector2.hpp>
+#include <algorithm>
+#include <cmath>
+#include <cstddef>
+#include <cstdint>
+#include <optional>
+#include <string>
+#include <vector>
+
+// Seconds between fruit spawns, unless configured otherwise.
+static constexpr float fruit_spawn_interval = 5.0f;
+
+// Seconds between each step the snake takes, and how short levelling up can make that.
+static constexpr float movement_interval = 0.25f;
+static constexpr float min_movement_interval = 0.05f;
+// Seconds taken off every move for each block the snake has grown by, with
+// `Config::length_speedup`.
+static constexpr float length_speedup_step = 0.01f;
+
+// Where the top-left block is drawn by default, and the smallest number of blocks a grid may have
+// across.
+static const sf::Vector2f grid_origin = sf::Vector2f(12.0f, 8.0f);
+static constexpr std::size_t min_grid_len = 5;
+
+// How much wider the window gets to the right of the grid to make room for the length meter.
+static constexpr float length_meter_width = 2.0f * block_len;
+
+static constexpr char const *default_font_path = "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf";
+
+static const sf::Color paint_colour = sf::Color(0x90, 0xEE, 0x90);
+
+// Where the snake's head starts out.
+enum class SpawnMode : uint8_t {
+  Random,
+  // The middle of the grid, for when every game should start out the same.
+  Center,
+};
+
+struct Config {
+  std::size_t horizontal = 19;
+  std::size_t vertical = 15;
+  // Where the top-left block is drawn, unless the grid is centred in the window instead.
+  sf::Vector2f origin = grid_origin;
+  bool centred = false;
+  // Random unless given, in which case every game plays out the same for the same inputs.
+  std::optional<std::uint32_t> seed;
+  SpawnMode spawn_mode = SpawnMode::Random;
+
+  // Running into the body cuts the snake off at that segment instead of ending the game.
+  bool shrink_on_collision = false;
+  // Hazard fruit such as `FruitKind::Reverse` spawn alongside the ordinary kind.
+  bool chaos = false;
+  // So does the occasional `FruitKind::Freeze`.
+  bool freeze = false;
+  // The chance of a `FruitKind::Bonus` turning up with each timed spawn, if given. There's only
+  // ever one, and it's gone again after `bonus_lifetime` seconds.
+  std::optional<float> bonus_chance;
+  float bonus_lifetime = 5.0f;
+  // Start moving right away instead of waiting for the first arrow key.
+  bool auto_start = false;
+  // Start moving right away too, but in any direction with room rather than the roomiest one.
+  // Which one comes from the seed like everything else.
+  bool random_start = false;
+  // How many turns pressed between two moves are kept for the moves after. The first one is always
+  // taken straight away. More makes quick sequences like a U-turn easier to pull off, but also lets
+  // turns pile up and play out long after the keys were pressed; with none, only one turn per move
+  // counts.
+  std::size_t input_buffer = 2;
+  // Pick the size of the grid with the keyboard before the first game.
+  bool setup = false;
+  // Keep the ghost and the lifetime stats on disk. Off for simulated games, which shouldn't touch
+  // the player's own.
+  bool keep_records = true;
+  // Ask before closing the window, or quitting with Escape, instead of going straight away.
+  bool confirm_quit = false;
+  // Open the level editor before the game starts.
+  bool editor = false;
+  float spawn_interval = fruit_spawn_interval;
+  // Pause by itself once no direction has been pressed for this many seconds, if given, in case
+  // the player has had to step away. Any key carries on.
+  std::optional<float> idle_pause;
+  // Seconds a move into a wall or off the grid waits for a turn before it ends the game. Off by
+  // default, as in classic snake.
+  float coyote_time = 0.0f;
+  // For practice, how many moves Backspace can take back, even the one that ended the game; none
+  // turns it off. Practice games aren't counted in the lifetime stats and leave no ghost.
+  std::size_t practice_undo = 0;
+  // Let the snake move diagonally too, by holding two arrow keys or with the numpad's corners.
+  bool diagonal = false;
+  // The head may move into the block the tail is leaving on the same tick. Turned off, that counts
+  // as running into the tail.
+  bool tail_safe = true;
+  // Give each new ordinary fruit a colour no other fruit on the board has, while there are any
+  // left.
+  bool distinct_fruit_colours = false;
+  // Only let the spawn timer add a fruit while there is none on the board.
+  bool single_fruit = false;
+  // Fruit left uneaten for this many seconds rot away, if given.
+  std::optional<float> fruit_lifetime;
+  // Shift each fruit's colour towards a rotten brown as its lifetime runs out. Does nothing without
+  // `fruit_lifetime`.
+  bool fruit_tint = false;
+  // Zen mode starts in a walled-in area about `zen_size` blocks across and opens it up by one
+  // ring every `zen_interval` seconds.
+  bool zen = false;
+  std::size_t zen_size = 5;
+  float zen_interval = 10.0f;
+  // Only spawn fruit the snake can actually get to.
+  bool fair_spawn = false;
+  // The snake stops growing at this length, if given.
+  std::optional<std::size_t> max_length;
+  // Cells that start out as walls, given as `[[x, y], ...]`.
+  std::vector<sf::Vector2u> walls;
+  // Where the fruit go, in the same form, for puzzles: the first as the game starts and the next
+  // each time one is eaten, with no other fruit spawning until the list runs out. After that there
+  // are no more fruit, or random ones as usual with `fruit_sequence_random`.
+  std::vector<sf::Vector2u> fruit_sequence;
+  bool fruit_sequence_random = false;
+  // How many fruit are on the board as soon as the snake starts moving.
+  std::size_t initial_fruits = 1;
+  // Keep this many fruit on the board per block, instead of `initial_fruits` and the spawn timer
+  // alone, so big boards aren't left bare; see `target_fruit`.
+  std::optional<float> fruit_density;
+  // Briefly slow down after the head narrowly avoids a wall or the body.
+  bool slow_motion = false;
+  // Outline every vacant block rather than just the edge of the grid. Toggled with G.
+  bool grid_lines = true;
+  // Draw fruit as circles rather than squares, so it can't be mistaken for the snake.
+  bool round_fruit = true;
+  // Tint every other block a little, to make the grid easier to read without lines. Toggled with B.
+  bool checkerboard = false;
+  // Draw in `theme::high_contrast` instead of the usual green on white. H switches back and forth.
+  bool high_contrast = false;
+  // The snake in this instead of the theme's colour, whichever theme it is.
+  std::optional<sf::Color> snake_colour;
+  // The snake leaves a painted trail behind. C clears it.
+  bool paint = false;
+  // A level is reached every `level_fruits` fruit eaten; the bar shows how close the next one is.
+  std::size_t level_fruits = 5;
+  bool progress_bar = false;
+  // Whether going up a level makes the game harder: the movement interval is multiplied by
+  // `level_speedup` and `level_walls` walls and `level_extra_fruits` fruit are added.
+  bool levels = false;
+  float level_speedup = 0.9f;
+  std::size_t level_walls = 1;
+  std::size_t level_extra_fruits = 0;
+  // Start slow and speed up smoothly with every fruit eaten, from `curve_start` seconds a move
+  // towards `curve_min`, closing the gap by a fraction `curve_rate` of the way each time. Takes
+  // the place of the speedup from levels.
+  bool speed_curve = false;
+  float curve_start = 0.5f;
+  float curve_min = 0.1f;
+  float curve_rate = 0.15f;
+  // Speed up as the snake grows, and slow back down if it shrinks: `speed_step` seconds come off
+  // `movement_interval` for every block past the head, down to `min_movement_interval`. Takes the
+  // place of the speedup from levels, and gives way to `speed_curve`.
+  bool length_speedup = false;
+  float speed_step = length_speedup_step;
+  // The snake's colour cycles through the rainbow.
+  bool rainbow = false;
+  // Stack a small square per segment of the body, behind the head, next to the grid.
+  bool length_meter = false;
+  // Fruit creeps towards the head every time the spawn timer goes off.
+  bool magnet = false;
+  // Flash the cell the snake died on for a while after the game ends.
+  bool death_marker = true;
+  // Show a small copy of the board in a bottom corner of the window, for grids too big to take in
+  // at a glance. Toggled with M.
+  bool minimap = false;
+  // Number every segment of the snake from the head, to debug the order of the body.
+  bool segment_numbers = false;
+  // Slide the snake from block to block instead of jumping a whole block every move. It's drawn a
+  // move behind to do so.
+  bool interpolate = true;
+  // Float the points each fruit was worth up from where it was eaten.
+  bool score_popups = false;
+  // Light up the background for a moment whenever a fruit is eaten.
+  bool eat_flash = false;
+  // Save the final board, score and all, as a timestamped PNG whenever a game ends.
+  bool end_image = false;
+  // Show the title screen, with a demo playing behind it, before the first game.
+  bool title_screen = true;
+  // A layout (see `Scenario`) to start every game from instead of an empty grid of this size.
+  std::string scenario_path;
+  // Text is only drawn if this font can be loaded.
+  std::string font_path = default_font_path;
+  // Where the ghost and the lifetime stats are kept, the working directory if empty.
+  std::string records_dir;
+  // Print the settings above and exit instead of playing.
+  bool print_info = false;
+};
+
+// Where a grid `blocks` across fits right in the middle of `area` pixels, with blocks `len` pixels
+// wide.
+inline sf::Vector2f centred_origin(sf::Vector2u area, sf::Vector2u blocks, float len) {
+  return sf::Vector2f((float(area.x) - float(blocks.x) * len) / 2.0f,
+                      (float(area.y) - float(blocks.y) * len) / 2.0f);
+}
+
+// How many fruit a grid of `len` blocks keeps on it with `Config::fruit_density`: never fewer than
+// one.
+inline std::size_t target_fruit(std::size_t len, float density) {
+  return std::max<std::size_t>(1, std::size_t(std::lround(float(len) * density)));
+}
+
+// Seconds a move under `Config::length_speedup` for a snake `length` blocks long, which never gets
+// below `min_movement_interval` however long it is.
+inline float length_interval(std::size_t length, const Config &config) {
+  const auto taken = config.speed_step * float(length > 0 ? length - 1 : 0);
+
+  return std::max(movement_interval - taken, min_movement_interval);
+}
+
+// Seconds a move under `Config::speed_curve`, once `fruit_eaten` fruit have been eaten.
+inline float curve_interval(std::size_t fruit_eaten, const Config &config) {
+  return config.curve_min + (config.curve_start - config.curve_min) *
+                              std::pow(1.0f - config.curve_rate, float(fruit_eaten));
+}
+
+// The grid plus a block's worth of margin around it, and the length meter if it's shown.
+inline sf::Vector2u window_size(const Config &config) {
+  const auto meter = config.length_meter ? length_meter_width : 0.0f;
+
+  // A block's worth of margin in all, or as much again on the far side as the origin leaves.
+  const auto origin = config.centred ? sf::Vector2f() : config.origin;
+  const auto margin =
+    sf::Vector2f(std::max(block_len, 2.0f * origin.x), std::max(block_len, 2.0f * origin.y));
+
+  return sf::Vector2u(unsigned(float(config.horizontal) * block_len + margin.x + meter),
+                      unsigned(float(config.vertical) * block_len + margin.y));
+}
+
+// Where `config` puts the grid in a window of `window` pixels. The length meter stays to the right
+// of a centred grid.
+inline sf::Vector2f origin_for(const Config &config, sf::Vector2u window) {
+  if (!config.centred)
+    return config.origin;
+
+  const auto meter = config.length_meter ? unsigned(length_meter_width) : 0u;
+
+  return centred_origin(sf::Vector2u(window.x - meter, window.y),
+                        sf::Vector2u(unsigned(config.horizontal), unsigned(config.vertical)),
+                        block_len);
+}
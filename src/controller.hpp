@@ -0,0 +1,92 @@
+#pragma once
+
+#include "grid.hpp"
+#include "snake.hpp"
+#include "utils.hpp"
+
+#include <cstddef>
+#include <deque>
+#include <optional>
+#include <utility>
+#include <vector>
+
+// Steers a snake. Asked before every move which way to turn, and gives none to carry on.
+class Controller {
+public:
+  virtual ~Controller() = default;
+
+  virtual std::optional<Direction> next_direction(const Snake &snake, const Grid &grid) = 0;
+};
+
+// The player's turns that came after the snake had already turned since its last move, taken
+// one per move.
+class HumanController : public Controller {
+  std::deque<Direction> queued;
+  std::size_t capacity;
+
+public:
+  explicit HumanController(std::size_t capacity) : capacity(capacity) {}
+
+  // False if there's no more room and the turn was dropped.
+  bool push(Direction direct) {
+    if (queued.size() >= capacity)
+      return false;
+
+    queued.push_back(direct);
+    return true;
+  }
+
+  bool waiting() const noexcept { return !queued.empty(); }
+
+  // The turn at the back of the line, if there is one.
+  std::optional<Direction> last() const {
+    if (queued.empty())
+      return std::nullopt;
+
+    return queued.back();
+  }
+
+  void clear() noexcept { queued.clear(); }
+
+  std::optional<Direction> next_direction(const Snake &, const Grid &) override {
+    if (queued.empty())
+      return std::nullopt;
+
+    const auto direct = queued.front();
+    queued.pop_front();
+
+    return direct;
+  }
+};
+
+// The autopilot.
+class AiController : public Controller {
+public:
+  std::optional<Direction> next_direction(const Snake &snake, const Grid &grid) override {
+    return autopilot(snake, grid);
+  }
+};
+
+// Turns recorded one per move, `Direction::None` for carrying on. Once they run out the snake goes
+// straight on.
+class ReplayController : public Controller {
+  std::vector<Direction> turns;
+  std::size_t next = 0;
+
+public:
+  explicit ReplayController(std::vector<Direction> turns) : turns(std::move(turns)) {}
+
+  bool finished() const noexcept { return next >= turns.size(); }
+
+  std::optional<Direction> next_direction(const Snake &, const Grid &) override {
+    if (finished())
+      return std::nullopt;
+
+    const auto direct = turns[next++];
+
+    if (direct == Direction::None)
+      return std::nullopt;
+
+    return direct;
+  }
+};
@@ -0,0 +1,161 @@
+#include "game.hpp"
+
+#include <SFML/Graphics.hpp>
+#include <cstddef>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <vector>
+
+Scenario parse_scenario(const std::string &text) {
+  std::vector<std::string> rows;
+
+  std::size_t start = 0;
+  while (start < text.size()) {
+    auto end = text.find('\n', start);
+    if (end == std::string::npos)
+      end = text.size();
+
+    auto row = text.substr(start, end - start);
+    if (!row.empty() && row.back() == '\r')
+      row.pop_back();
+
+    rows.push_back(row);
+    start = end + 1;
+  }
+
+  while (!rows.empty() && rows.back().empty())
+    rows.pop_back();
+
+  if (rows.empty() || rows.front().empty())
+    throw std::runtime_error("the scenario is empty");
+
+  Scenario scenario;
+  scenario.horizontal = rows.front().size();
+  scenario.vertical = rows.size();
+  scenario.blocks.resize(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+
+  std::optional<sf::Vector2u> head;
+  // Indexed by segment number minus one.
+  std::vector<std::optional<sf::Vector2u>> segments;
+
+  for (std::size_t y = 0; y < rows.size(); y++) {
+    if (rows[y].size() != scenario.horizontal)
+      throw std::runtime_error("row " + std::to_string(y + 1) + " isn't as long as the first");
+
+    for (std::size_t x = 0; x < rows[y].size(); x++) {
+      const auto c = rows[y][x];
+      const auto pos = sf::Vector2u(x, y);
+      auto &block = scenario.blocks[x + y * scenario.horizontal];
+
+      std::optional<std::size_t> segment;
+      std::optional<Direction> facing;
+
+      switch (c) {
+      case '.':
+        break;
+      case '#':
+        block = BlockType::Wall;
+        break;
+      case '*':
+        block = BlockType::OccupiedFruit;
+        break;
+      case '@':
+        facing = Direction::None;
+        break;
+      case '<':
+        facing = Direction::Left;
+        break;
+      case '>':
+        facing = Direction::Right;
+        break;
+      case '^':
+        facing = Direction::Up;
+        break;
+      case 'v':
+        facing = Direction::Down;
+        break;
+      default:
+        if (c >= '1' && c <= '9') {
+          segment = std::size_t(c - '1');
+        } else if (c >= 'A' && c <= 'Z') {
+          segment = std::size_t(c - 'A') + 9;
+        } else {
+          throw std::runtime_error(std::string("unknown character '") + c + "' at " +
+                                   to_string(sf::Vector2i(pos)));
+        }
+      }
+
+      if (facing) {
+        if (head)
+          throw std::runtime_error("more than one head, the second at " +
+                                   to_string(sf::Vector2i(pos)));
+
+        head = pos;
+        scenario.direction = *facing;
+      }
+
+      if (segment) {
+        if (*segment >= segments.size())
+          segments.resize(*segment + 1);
+        if (segments[*segment])
+          throw std::runtime_error(std::string("segment '") + c + "' appears more than once");
+
+        segments[*segment] = pos;
+      }
+    }
+  }
+
+  if (!head)
+    throw std::runtime_error("there's no head");
+
+  scenario.head = *head;
+
+  auto previous = *head;
+  for (std::size_t i = 0; i < segments.size(); i++) {
+    if (!segments[i])
+      throw std::runtime_error("segment " + std::to_string(i + 1) + " is missing");
+
+    const auto pos = *segments[i];
+    const auto dx = pos.x > previous.x ? pos.x - previous.x : previous.x - pos.x;
+    const auto dy = pos.y > previous.y ? pos.y - previous.y : previous.y - pos.y;
+
+    if (dx + dy != 1)
+      throw std::runtime_error("segment " + std::to_string(i + 1) +
+                               " isn't next to the one in front of it");
+
+    scenario.body.push_back(pos);
+    previous = pos;
+  }
+
+  if (!scenario.body.empty() && scenario.direction != Direction::None &&
+      scenario.head + to_pos(scenario.direction) == scenario.body.front())
+    throw std::runtime_error("the head faces straight into its own body");
+
+  return scenario;
+}
+
+void draw_overlay(sf::RenderTarget &target, const sf::Font *font, const std::string &label,
+                  unsigned size) {
+  const auto &view = target.getView();
+
+  sf::RectangleShape dim(view.getSize());
+  dim.setPosition(view.getCenter() - view.getSize() / 2.0f);
+  dim.setFillColor(sf::Color(0, 0, 0, 0x80));
+  target.draw(dim);
+
+  if (!font)
+    return;
+
+  sf::Text text(label, *font, size);
+  const auto bounds = text.getLocalBounds();
+  text.setOrigin(bounds.left + bounds.width / 2.0f, bounds.top + bounds.height / 2.0f);
+  text.setPosition(view.getCenter());
+  text.setFillColor(sf::Color::White);
+  target.draw(text);
+}
+
+std::string stats(std::size_t score, std::size_t steps, std::size_t length) {
+  return " (score: " + std::to_string(score) + ", steps: " + std::to_string(steps) +
+         ", length: " + std::to_string(length) + ")";
+}
@@ -0,0 +1,1590 @@
+#pragma once
+
+#include "colour.hpp"
+#include "config.hpp"
+#include "controller.hpp"
+#include "grid.hpp"
+#include "json.hpp"
+#include "snake.hpp"
+#include "stats.hpp"
+#include "theme.hpp"
+#include "utils.hpp"
+
+#include <SFML/Graphics.hpp>
+#include <SFML/System/Vector2.hpp>
+#include <algorithm>
+#include <cmath>
+#include <cstddef>
+#include <cstdint>
+#include <deque>
+#include <fstream>
+#include <functional>
+#include <iostream>
+#include <memory>
+#include <optional>
+#include <sstream>
+#include <stdexcept>
+#include <string>
+#include <unordered_map>
+#include <utility>
+#include <vector>
+
+// The ghost of the previous run is drawn translucent.
+static const sf::Color ghost_colour = sf::Color(0x00, 0x80, 0x00, 0x60);
+static const sf::Color progress_colour = sf::Color(0x00, 0x80, 0x00);
+// What a fruit has turned to by the end of its lifetime, with `Config::fruit_tint`.
+static const sf::Color rotten_colour = sf::Color(0x6B, 0x3A, 0x1E);
+// Pixels per block in the minimap, at most, and how much of the window it may take up each way.
+static constexpr float minimap_scale = 3.0f;
+static constexpr float minimap_share = 0.25f;
+static const sf::Color minimap_background = sf::Color(0x00, 0x00, 0x00, 0x80);
+// The death marker alternates between these.
+static const sf::Color death_colours[] = {sf::Color::Red, sf::Color::Yellow};
+
+// Lets the player lay out walls, the snake's starting cell and fruit with the mouse before the
+// game starts.
+class Editor {
+  Grid &grid;
+  Snake &snake;
+
+  // What dragging the mouse paints, decided by the block the drag started on.
+  std::optional<BlockType> brush;
+  sf::Vector2f cursor;
+
+  // The blocks that were edited, most recent last, along with what they were before.
+  static constexpr std::size_t max_history = 64;
+  std::deque<std::pair<std::size_t, BlockType>> history;
+
+  std::optional<std::size_t> cursor_cell() const { return grid.cell_at_pixel(cursor.x, cursor.y); }
+
+  void set_cell(std::size_t pos, BlockType type) {
+    if (history.size() == max_history)
+      history.pop_front();
+
+    history.emplace_back(pos, grid[pos].type());
+
+    grid.set_type(pos, type);
+  }
+
+  void paint(std::size_t pos) {
+    if (!brush || grid[pos].type() == *brush)
+      return;
+    if (grid[pos].type() != BlockType::Vacant && grid[pos].type() != BlockType::Wall)
+      return;
+
+    set_cell(pos, *brush);
+
+    const auto &colours = theme::current();
+    grid.set_colour(pos, *brush == BlockType::Wall ? colours.wall : colours.lines);
+  }
+
+public:
+  Editor(Grid &grid, Snake &snake) : grid(grid), snake(snake), brush(), cursor(), history() {}
+
+  void press(sf::Vector2f point) {
+    cursor = point;
+
+    auto pos = cursor_cell();
+    if (!pos)
+      return;
+
+    switch (grid[*pos].type()) {
+    case BlockType::Vacant:
+      brush = BlockType::Wall;
+      break;
+    case BlockType::Wall:
+      brush = BlockType::Vacant;
+      break;
+    default:
+      return;
+    }
+
+    paint(*pos);
+  }
+
+  void drag(sf::Vector2f point) {
+    cursor = point;
+
+    if (auto pos = cursor_cell())
+      paint(*pos);
+  }
+
+  void release() { brush.reset(); }
+
+  void place_snake() {
+    if (auto pos = cursor_cell())
+      snake.relocate(grid.to_xy(*pos));
+  }
+
+  void place_fruit() {
+    auto pos = cursor_cell();
+    if (!pos || is_occupied(grid[*pos].type()))
+      return;
+
+    set_cell(*pos, BlockType::OccupiedFruit);
+    grid.set_fruit(*pos, FruitKind::Normal);
+    grid.set_colour(*pos, gen_fruit_colour());
+  }
+
+  // Reverts the most recent wall or fruit edit.
+  void undo() {
+    while (!history.empty()) {
+      auto [pos, type] = history.back();
+      history.pop_back();
+
+      // The snake may have been moved onto the block since.
+      if (grid[pos].type() == BlockType::OccupiedSnake)
+        continue;
+
+      grid.set_type(pos, type);
+
+      const auto &colours = theme::current();
+      grid.set_colour(pos, type == BlockType::Wall ? colours.wall : colours.lines);
+      return;
+    }
+  }
+
+  bool save(const std::string &path) const {
+    std::ofstream file(path);
+    file << to_ascii_map(grid, snake);
+
+    return bool(file);
+  }
+};
+
+// The walled-in playable area of zen mode.
+class Arena {
+  Grid &grid;
+
+  // How many rings, counted from the edge of the grid, are still walls.
+  std::size_t walled;
+  // The blocks the arena walled off itself, by ring. Only those are opened up again, so walls that
+  // were already there stay.
+  std::vector<std::vector<std::size_t>> rings;
+
+  std::size_t ring_of(std::size_t pos) const {
+    const auto xy = grid.to_xy(pos);
+
+    return std::min({std::size_t(xy.x), std::size_t(xy.y), grid.horizontal() - 1 - xy.x,
+                     grid.vertical() - 1 - xy.y});
+  }
+
+public:
+  Arena(Grid &grid, std::size_t size) : grid(grid), walled(0) {
+    const auto side = std::min(grid.horizontal(), grid.vertical());
+
+    walled = (side - std::min(size, side)) / 2;
+    rings.resize(walled);
+
+    for (std::size_t i = 0; i < grid.len(); i++) {
+      if (ring_of(i) < walled && grid[i].type() == BlockType::Vacant) {
+        grid.set_type(i, BlockType::Wall);
+        grid.set_colour(i, theme::current().wall);
+        rings[ring_of(i)].push_back(i);
+      }
+    }
+  }
+
+  // Opens up the innermost walled ring. Returns false once the arena covers the whole grid.
+  bool expand() {
+    if (walled == 0)
+      return false;
+
+    walled--;
+
+    for (const auto pos : rings[walled]) {
+      if (pos < grid.len() && grid[pos].type() == BlockType::Wall) {
+        grid.set_type(pos, BlockType::Vacant);
+        grid.set_colour(pos, theme::current().lines);
+      }
+    }
+
+    return true;
+  }
+};
+
+// The previous run's head, replayed tick by tick on top of the current one. Purely visual.
+class Ghost : public sf::Drawable {
+  // What the last run left behind, and what this run is leaving.
+  std::vector<sf::Vector2u> previous;
+  std::vector<sf::Vector2u> current;
+
+  sf::Vector2f origin;
+  std::size_t tick = 0;
+
+  void draw(sf::RenderTarget &target, sf::RenderStates states) const override {
+    if (tick >= previous.size())
+      return;
+
+    sf::RectangleShape shape(sf::Vector2f(block_len, block_len));
+    shape.setPosition(origin + sf::Vector2f(float(previous[tick].x) * block_len,
+                                            float(previous[tick].y) * block_len));
+    shape.setFillColor(ghost_colour);
+
+    target.draw(shape, states);
+  }
+
+public:
+  explicit Ghost(sf::Vector2f origin) : origin(origin) {}
+
+  // A missing or unreadable file just means there's no ghost.
+  void load(const std::string &path) {
+    previous.clear();
+
+    std::ifstream file(path);
+    unsigned x, y;
+    while (file >> x >> y) {
+      previous.emplace_back(x, y);
+    }
+  }
+
+  void save(const std::string &path) const {
+    std::ofstream file(path);
+    if (!file)
+      throw std::runtime_error("couldn't write to " + path);
+
+    for (const auto &pos : current) {
+      file << pos.x << ' ' << pos.y << '\n';
+    }
+  }
+
+  // Called with the head once when the game starts and then after every move.
+  void record(sf::Vector2u head) {
+    tick = current.size();
+    current.push_back(head);
+  }
+};
+
+// Where F5 and F9 export and import the game state.
+static constexpr char const *state_path = "snek.json";
+
+static constexpr char const *title = "Snek";
+
+// Where the level editor writes its layout.
+static constexpr char const *level_path = "level.txt";
+
+// Where each run's head positions are kept, to be replayed as the next run's ghost.
+static constexpr char const *ghost_path = "ghost.txt";
+
+// Where the totals over every game are kept.
+static constexpr char const *stats_path = "stats.json";
+
+// How many degrees around the colour wheel the rainbow snake goes every second.
+static constexpr float rainbow_speed = 90.0f;
+
+// How long the death marker flashes for, and how long each of its colours lasts.
+static constexpr float death_flash_duration = 2.0f;
+static constexpr float death_flash_period = 0.2f;
+
+// How long a score popup lasts and how many pixels it rises in that time.
+static constexpr float popup_duration = 0.8f;
+static constexpr float popup_rise = 24.0f;
+
+// How long the background takes to fade back from the theme's flash colour after eating.
+static constexpr float eat_flash_duration = 0.1f;
+
+// What a `FruitKind::Bonus` is worth, and how long before it goes it starts blinking, and how fast.
+static constexpr std::size_t bonus_points = 5;
+static constexpr float bonus_warning = 2.0f;
+static constexpr float bonus_blink_period = 0.2f;
+
+// How long the controls stay inverted after eating a `FruitKind::Reverse`, and what the window
+// title says meanwhile.
+static constexpr float reverse_duration = 5.0f;
+static constexpr char const *reverse_message = "controls reversed!";
+// How long the snake stands still after eating a `FruitKind::Freeze`.
+static constexpr float freeze_duration = 2.0f;
+
+// A near miss stretches the movement interval by `slow_motion_factor` for `slow_motion_duration`
+// seconds, and can't do so again until `slow_motion_cooldown` seconds after that.
+static constexpr float slow_motion_duration = 0.75f;
+static constexpr float slow_motion_factor = 2.5f;
+static constexpr float slow_motion_cooldown = 2.0f;
+
+enum class GameStates : uint8_t {
+  // The autopilot plays a demo behind the title until a key is pressed.
+  Title,
+  // Picking the size of the grid, which is shown as it would be.
+  Setup,
+  Editor,
+  Start,
+  InProgress,
+  Paused,
+  End,
+  // The snake filled every block there was.
+  Won,
+};
+
+// Something that happened in a game, for whatever the game is embedded in to react to.
+struct GameEvent {
+  enum class Kind : std::uint8_t { Moved, Ate, LevelledUp, Ended };
+
+  Kind kind;
+  // Where the head is, or for `Ended` by a death, where the fatal move tried to take it.
+  sf::Vector2i pos;
+  // Both as of the event.
+  std::size_t score;
+  std::size_t level;
+  // What was eaten, for `Ate`, and what the game ended by, for `Ended`.
+  std::optional<FruitKind> fruit;
+  std::optional<Ending> ending;
+};
+
+// A situation to practise, laid out like `to_ascii_map` does but with the body numbered from the
+// head, `1` to `9` and then `A` to `Z`, and the head drawn as `<`, `>`, `^` or `v` to give its
+// direction, or `@` for none.
+struct Scenario {
+  std::size_t horizontal = 0;
+  std::size_t vertical = 0;
+  // Only walls and fruit; the snake's own blocks are left vacant here.
+  std::vector<BlockType> blocks;
+
+  sf::Vector2u head;
+  std::vector<sf::Vector2u> body;
+  Direction direction = Direction::None;
+};
+
+// Throws `std::runtime_error` describing the first thing wrong with the layout.
+Scenario parse_scenario(const std::string &text);
+
+// Dims the whole view and writes `label` in the middle of it.
+void draw_overlay(sf::RenderTarget &target, const sf::Font *font, const std::string &label,
+                  unsigned size = 40);
+
+// How the game went, for the end of it.
+std::string stats(std::size_t score, std::size_t steps, std::size_t length);
+
+// A whole game of snek: the grid and everything on it, the state it's in and its timers. Whoever
+// owns the window feeds it input and time, and has it draw itself.
+class Game {
+  // Kept by value; `Snake` holds on to it.
+  Config _config;
+  sf::Vector2u _resolution;
+
+  // `Snake`, `Arena` and `Editor` refer back to the grid, hence the order.
+  Grid _grid;
+  std::optional<Arena> _arena;
+  Snake _snake;
+  Editor _editor;
+  Ghost _ghost;
+
+  GameStates _state = GameStates::Start;
+  // Demos play themselves, and don't leave a ghost behind.
+  bool _demo;
+  // Shown in the window title after the name of the game.
+  std::string _message;
+
+  float movement_seconds = 0.0f;
+  // The snake's blocks from the head, as they were before its last move. Only drawn.
+  std::vector<sf::Vector2u> moved_from;
+  float spawn_seconds = 0.0f;
+  float reverse_seconds = 0.0f;
+  // Only the movement timer stops while frozen; everything else carries on.
+  float freeze_seconds = 0.0f;
+  float zen_seconds = 0.0f;
+  // Counts down through the slow motion and then its cooldown.
+  float slow_motion_seconds = 0.0f;
+
+  // Whether the snake has turned since its last move, and the turns pressed after that, which are
+  // taken one per move.
+  bool turned = false;
+  HumanController human{_config.input_buffer};
+  // Steers instead of the player when set, like the autopilot in a demo.
+  std::unique_ptr<Controller> _controller;
+
+  // Seconds since a direction was last pressed, and whether that's what the game is paused for.
+  float idle_seconds = 0.0f;
+  bool idle_paused = false;
+
+  // Successful moves since the game started.
+  std::size_t steps = 0;
+  // One point for every fruit eaten, or `bonus_points` for a bonus.
+  std::size_t _score = 0;
+  std::size_t fruit_eaten = 0;
+  std::size_t _level = 1;
+  // Starts out as `movement_interval` and shrinks with every level when levels are on, with
+  // every fruit along `Config::speed_curve`, or as the snake grows with `Config::length_speedup`.
+  float interval = movement_interval;
+
+  // Seconds since the game was made, for anything animated.
+  float elapsed = 0.0f;
+  // When the last fruit was eaten, as far as `elapsed` goes; only the background looks at it.
+  std::optional<float> eaten_at;
+
+  // The points for a fruit, shown from the middle of its block from `elapsed` time `shown_at` on.
+  // Only drawn, never played with.
+  struct Popup {
+    sf::Vector2f pos;
+    std::string label;
+    float shown_at;
+  };
+  std::vector<Popup> popups;
+
+  // How long each fruit on the board has been there, by block, and the colour it came in, which
+  // the tint starts from. Only kept with `Config::fruit_lifetime` or bonus fruit.
+  struct FruitAge {
+    float seconds;
+    sf::Color colour;
+  };
+  std::unordered_map<std::size_t, FruitAge> fruit_ages;
+
+  // Everything a move can change, as it was before each of the last `Config::practice_undo` moves,
+  // the latest at the back.
+  struct Snapshot {
+    // The score goes along with the board.
+    std::string board;
+    std::size_t steps;
+    std::size_t fruit_eaten;
+    std::size_t level;
+    float interval;
+    float reverse_seconds;
+    float freeze_seconds;
+    std::size_t spawner_position;
+    std::unordered_map<std::size_t, FruitAge> fruit_ages;
+  };
+  std::deque<Snapshot> rewind;
+
+  // How many fruit to keep on the board with `Config::fruit_density`, worked out as the game
+  // starts.
+  std::size_t fruit_target = 0;
+
+  // Set by loading a scenario, whose fruit are all already in place.
+  bool scripted = false;
+  // Where every fruit goes, those of `Config::fruit_sequence` included.
+  std::unique_ptr<FruitSpawner> _spawner = make_spawner(_config);
+
+  // Where the fatal move tried to take the head, as reported by its exception, and how long ago
+  // that was.
+  std::optional<sf::Vector2i> death;
+  float end_seconds = 0.0f;
+
+  std::function<void(const GameEvent &)> observer;
+
+  void emit(GameEvent::Kind kind, sf::Vector2i pos, std::optional<FruitKind> fruit = std::nullopt,
+            std::optional<Ending> ending = std::nullopt) const {
+    if (observer) {
+      observer(GameEvent{kind, pos, _score, _level, fruit, ending});
+    }
+  }
+
+  // `name` in `Config::records_dir`.
+  std::string record_path(const char *name) const {
+    return _config.records_dir.empty() ? name : _config.records_dir + "/" + name;
+  }
+
+  // Read by demos too, for the title screen, but only real games add to it.
+  LifetimeStats _lifetime = load_stats(record_path(stats_path));
+
+  void end(const char *what, Ending ending) {
+    _state = ending == Ending::Won ? GameStates::Won : GameStates::End;
+
+    emit(GameEvent::Kind::Ended, death.value_or(sf::Vector2i(_snake.head())), std::nullopt, ending);
+
+    if (_demo)
+      return;
+
+    _message =
+      std::string(what) + (ending == Ending::Won ? " - you won!" : " - over!") +
+      stats(_score, steps, _snake.len());
+
+    if (practising()) {
+      _message += " (Backspace rewinds)";
+      return;
+    }
+
+    // A broken game proves nothing either way, and its ghost couldn't be trusted.
+    if (!_config.keep_records || ending == Ending::Error)
+      return;
+
+    _lifetime.record(ending, fruit_eaten, _snake.len());
+
+    try {
+      _ghost.save(record_path(ghost_path));
+      save_stats(_lifetime, record_path(stats_path));
+    } catch (std::runtime_error const &ex) {
+      std::cerr << ex.what() << '\n';
+    }
+  }
+
+  // The configured walls have to be in place before the snake picks a cell to start on.
+  static Grid make_grid(const Config &config, sf::Vector2u resolution) {
+    Grid grid(config.horizontal, config.vertical, origin_for(config, resolution), resolution);
+
+    for (const auto pos : config.walls) {
+      // A scenario may have made the grid smaller than the one the walls were checked against.
+      if (pos.x >= grid.horizontal() || pos.y >= grid.vertical())
+        continue;
+
+      grid.set_type(pos, BlockType::Wall);
+      grid.set_colour(pos, theme::current().wall);
+    }
+
+    return grid;
+  }
+
+  // Walls go up anywhere but right around the head, so they never appear in the snake's face.
+  void add_wall() {
+    static constexpr std::size_t attempts = 16;
+    static constexpr std::size_t clearance = 2;
+
+    const auto head = _grid.to_index(_snake.head());
+
+    for (std::size_t attempt = 0; attempt < attempts && _grid.free_count() > 0; attempt++) {
+      const auto pos = get_block_randomly(_grid);
+
+      if (_grid.manhattan(pos, head) > clearance) {
+        _grid.set_type(pos, BlockType::Wall);
+        _grid.set_colour(pos, theme::current().wall);
+        return;
+      }
+    }
+  }
+
+  void level_up() {
+    _level = level_for_score(_score);
+    emit(GameEvent::Kind::LevelledUp, sf::Vector2i(_snake.head()));
+
+    if (!_config.levels)
+      return;
+
+    if (!_config.speed_curve && !_config.length_speedup) {
+      interval = std::max(interval * _config.level_speedup, min_movement_interval);
+    }
+
+    for (std::size_t i = 0; i < _config.level_walls; i++) {
+      add_wall();
+    }
+
+    // The sequence has already decided where every fruit goes.
+    if (sequenced())
+      return;
+
+    for (std::size_t i = 0; i < _config.level_extra_fruits && _grid.free_count() > 0; i++) {
+      spawn_fruit(_grid, _snake, _config, *_spawner);
+    }
+  }
+
+  // Whether fruit come from `Config::fruit_sequence`, one at a time, rather than at random.
+  bool sequenced() const noexcept { return _spawner->sequenced(); }
+
+  // Puts down the next fruit of the sequence. Once it runs out, fruit either stop or turn random.
+  void spawn_next() { spawn_fruit(_grid, _snake, _config, *_spawner); }
+
+  // Ages every fruit by `secs`, picking up any that are new since last time, and lets the ones past
+  // their lifetime rot away. Bonus fruit have a lifetime of their own, and blink instead of
+  // turning brown.
+  void age_fruit(float secs) {
+    std::unordered_map<std::size_t, FruitAge> aged;
+    bool rotted = false;
+
+    for (const auto pos : _grid.fruit_positions()) {
+      const auto found = fruit_ages.find(pos);
+      auto fruit = found != fruit_ages.end() ? found->second : FruitAge{0.0f, _grid[pos].colour()};
+
+      fruit.seconds += secs;
+
+      const bool bonus = _grid[pos].fruit() == FruitKind::Bonus;
+      const auto lifetime = bonus ? std::optional(_config.bonus_lifetime) : _config.fruit_lifetime;
+
+      if (lifetime && fruit.seconds >= *lifetime) {
+        _grid.set_type(pos, BlockType::Vacant);
+        _grid.set_colour(pos, theme::current().lines);
+        rotted = rotted || !bonus;
+        continue;
+      }
+
+      if (bonus) {
+        const auto left = *lifetime - fruit.seconds;
+        const bool off = left < bonus_warning && int(left / bonus_blink_period) % 2 == 1;
+
+        _grid.set_colour(pos, off ? theme::current().lines : fruit.colour);
+      } else if (lifetime && _config.fruit_tint) {
+        _grid.set_colour(pos, colour::lerp(fruit.colour, rotten_colour, fruit.seconds / *lifetime));
+      }
+
+      aged.emplace(pos, fruit);
+    }
+
+    // Anything not picked up again has been eaten.
+    fruit_ages = std::move(aged);
+
+    // A sequence only moves on when its fruit goes, however that happens.
+    if (rotted && sequenced()) {
+      spawn_next();
+    }
+  }
+
+  // Spawns fruit until there are `fruit_target`, or the board is full.
+  void top_up_fruit() {
+    while (_grid.fruit_count() < fruit_target) {
+      if (!spawn_fruit(_grid, _snake, _config, *_spawner))
+        break;
+    }
+  }
+
+  // Rolls for a bonus fruit, unless there's one out already or the fruit are sequenced.
+  void maybe_spawn_bonus() {
+    if (!_config.bonus_chance || sequenced() || _grid.free_count() == 0)
+      return;
+
+    const auto fruit = _grid.fruit_positions();
+    if (std::any_of(fruit.begin(), fruit.end(),
+                    [&](auto pos) { return _grid[pos].fruit() == FruitKind::Bonus; }))
+      return;
+
+    // In thousandths, which is as fine as anyone will want to set it.
+    if (randomiser::gen(0, 999) >= std::size_t(*_config.bonus_chance * 1000.0f))
+      return;
+
+    // From the same strategy as every other fruit, so fair spawning covers bonuses too.
+    const auto pos = _spawner->pick(_grid, _snake);
+    if (!pos)
+      return;
+
+    _grid.set_type(*pos, BlockType::OccupiedFruit);
+    _grid.set_fruit(*pos, FruitKind::Bonus);
+    _grid.set_colour(*pos, fruit_colour(FruitKind::Bonus));
+  }
+
+  bool practising() const noexcept { return _config.practice_undo > 0 && !_demo; }
+
+  void remember() {
+    if (rewind.size() == _config.practice_undo) {
+      rewind.pop_front();
+    }
+
+    rewind.push_back(Snapshot{to_json(_snake, _grid, _score), steps, fruit_eaten, _level, interval,
+                              reverse_seconds, freeze_seconds, _spawner->position(), fruit_ages});
+  }
+
+  void step() {
+    if (practising()) {
+      remember();
+    }
+
+    try {
+      Controller &steering = _controller ? *_controller : human;
+
+      // A turn pressed since the last move is this move's, and anything in line waits for the next.
+      if (!turned) {
+        if (const auto direct = steering.next_direction(_snake, _grid)) {
+          turn(*direct);
+        }
+      }
+
+      moved_from = _snake.cells();
+      auto eaten = _snake.move();
+      steps++;
+      turned = false;
+
+      // After every move rather than every fruit, since there's more than eating that changes
+      // the length.
+      if (_config.length_speedup && !_config.speed_curve) {
+        interval = length_interval(_snake.len(), _config);
+      }
+
+      _ghost.record(_snake.head());
+      emit(GameEvent::Kind::Moved, sf::Vector2i(_snake.head()));
+
+      if (eaten) {
+        const auto points = eaten == FruitKind::Bonus ? bonus_points : 1;
+
+        _score += points;
+        fruit_eaten++;
+
+        if (_config.score_popups) {
+          popups.push_back(
+            Popup{_grid[_snake.head()].centre(), "+" + std::to_string(points), elapsed});
+        }
+
+        if (_config.speed_curve) {
+          interval = curve_interval(fruit_eaten, _config);
+        }
+        eaten_at = elapsed;
+        emit(GameEvent::Kind::Ate, sf::Vector2i(_snake.head()), eaten);
+
+        if (sequenced()) {
+          spawn_next();
+        }
+
+        top_up_fruit();
+
+        // Nothing left to eat and nowhere for more to go.
+        if (_grid.free_count() == 0 && _grid.fruit_count() == 0) {
+          end("filled the grid", Ending::Won);
+          return;
+        }
+
+        if (level_for_score(_score) > _level) {
+          level_up();
+        }
+      }
+
+      if (_config.slow_motion && _snake.near_miss() && slow_motion_seconds <= 0.0f) {
+        slow_motion_seconds = slow_motion_duration + slow_motion_cooldown;
+      }
+
+      if (eaten == FruitKind::Reverse) {
+        reverse_seconds = reverse_duration;
+        _message = reverse_message;
+      } else if (eaten == FruitKind::Freeze) {
+        freeze_seconds = freeze_duration;
+      }
+    } catch (OutOfBoundsException const &ex) {
+      death = ex.pos;
+      end(ex.what(), Ending::OutOfBounds);
+    } catch (CollisionException const &ex) {
+      death = sf::Vector2i(ex.pos);
+      end(ex.what(), Ending::Collision);
+    } catch (WallException const &ex) {
+      death = sf::Vector2i(ex.pos);
+      end(ex.what(), Ending::Wall);
+    } catch (std::logic_error const &ex) {
+      // From `Snake::validate`, or only `Snake::check_overlap` in a release build, after a move
+      // left the body in pieces.
+      end(("the game broke, " + std::string(ex.what())).c_str(), Ending::Error);
+    }
+  }
+
+  // Right, unless the snake would have more room heading somewhere else.
+  Direction start_direction() const {
+    auto best = Direction::Right;
+    auto most_room = safe_space(_snake, _grid, best);
+
+    for (const auto direct : all_directions) {
+      const auto room = safe_space(_snake, _grid, direct);
+
+      if (room > most_room) {
+        best = direct;
+        most_room = room;
+      }
+    }
+
+    return best;
+  }
+
+  // Any direction that doesn't end the game on the first move, or `start_direction` if they all do.
+  Direction random_start_direction() const {
+    std::vector<Direction> open;
+
+    for (const auto direct : all_directions) {
+      if (safe_space(_snake, _grid, direct) > 0) {
+        open.push_back(direct);
+      }
+    }
+
+    if (open.empty())
+      return start_direction();
+
+    return open[randomiser::gen(0, open.size() - 1)];
+  }
+
+  // Checked against the way the snake is heading by the time the turn is taken.
+  void turn(Direction direct) {
+    if (direct == _snake.direction())
+      return;
+
+    try {
+      _snake.set_direction(direct);
+      turned = true;
+    } catch (MotorException const &ex) {
+      _message = ex.what();
+    }
+  }
+
+  // Whether the next move would take the head off the grid or into a wall.
+  bool heading_for_wall() const {
+    const auto next = _snake.next_head();
+
+    if (next.x < 0 || next.y < 0 || std::size_t(next.x) >= _grid.horizontal() ||
+        std::size_t(next.y) >= _grid.vertical())
+      return true;
+
+    return _grid[sf::Vector2u(next)].type() == BlockType::Wall;
+  }
+
+  // What moving time forward does in the current state.
+  void update(float secs) {
+    switch (_state) {
+    case GameStates::Setup:
+    case GameStates::Editor:
+      break;
+    case GameStates::Start:
+      if (_snake.direction() == Direction::None) {
+        if (_config.random_start) {
+          _snake.set_direction(random_start_direction());
+        } else if (_config.auto_start) {
+          _snake.set_direction(start_direction());
+        }
+      }
+
+      if (_snake.direction() != Direction::None) {
+        _state = GameStates::InProgress;
+        _ghost.record(_snake.head());
+
+        if (_config.fruit_density && !sequenced()) {
+          fruit_target = target_fruit(_grid.len(), *_config.fruit_density);
+        }
+
+        if (sequenced()) {
+          spawn_next();
+        } else if (fruit_target > 0) {
+          top_up_fruit();
+        } else {
+          // Like walls, kept out of the snake's face, so none is eaten before the player has done
+          // anything.
+          static constexpr std::size_t clearance = 2;
+          ClearOfHeadSpawner clear(*_spawner, clearance);
+
+          for (std::size_t i = 0;
+               i < _config.initial_fruits && _grid.free_count() > 0 && !scripted; i++) {
+            spawn_fruit(_grid, _snake, _config, clear);
+          }
+        }
+      }
+
+      break;
+    case GameStates::Title:
+    case GameStates::InProgress: {
+      spawn_seconds += secs;
+
+      if (freeze_seconds > 0.0f) {
+        freeze_seconds = std::max(freeze_seconds - secs, 0.0f);
+
+        // Counts down in tenths of a second.
+        const auto tenths = int(std::ceil(freeze_seconds * 10.0f));
+        _message = tenths > 0 ? "frozen for " + std::to_string(tenths / 10) + "." +
+                                  std::to_string(tenths % 10) + "s"
+                              : "";
+      } else {
+        movement_seconds += secs;
+      }
+
+      if (reverse_seconds > 0.0f) {
+        reverse_seconds -= secs;
+
+        // Whatever has been said since stays.
+        if (reverse_seconds <= 0.0f) {
+          reverse_seconds = 0.0f;
+
+          if (_message == reverse_message) {
+            _message.clear();
+          }
+        }
+      }
+
+      if (_arena) {
+        zen_seconds += secs;
+
+        if (zen_seconds >= _config.zen_interval) {
+          _arena->expand();
+
+          zen_seconds = 0.0f;
+        }
+      }
+
+      if (spawn_seconds >= _config.spawn_interval) {
+        // Before spawning, so the new fruit stays where it appeared for a whole interval.
+        if (_config.magnet) {
+          // A fruit keeps its age wherever the magnet pulls it. The moves are in the order they
+          // were made, so one fruit taking another's old block works out.
+          for (const auto &[from, to] : attract_fruit(_grid, _snake)) {
+            const auto found = fruit_ages.find(from);
+
+            if (found != fruit_ages.end()) {
+              fruit_ages.insert_or_assign(to, found->second);
+              fruit_ages.erase(from);
+            }
+          }
+        }
+
+        if (fruit_target > 0) {
+          top_up_fruit();
+        } else if (!sequenced() && (!_config.single_fruit || _grid.fruit_count() == 0)) {
+          spawn_fruit(_grid, _snake, _config, *_spawner);
+        }
+
+        maybe_spawn_bonus();
+
+        spawn_seconds = 0.0f;
+      }
+
+      slow_motion_seconds = std::max(slow_motion_seconds - secs, 0.0f);
+
+      if (_config.idle_pause && _state == GameStates::InProgress) {
+        idle_seconds += secs;
+
+        if (idle_seconds >= *_config.idle_pause) {
+          _state = GameStates::Paused;
+          idle_paused = true;
+          _message = "paused while nothing was pressed, any key carries on";
+          break;
+        }
+      }
+
+      const auto due = move_due();
+      // A move into a wall is held back a little longer, in case the turn away comes just late.
+      bool held = !_demo && movement_seconds < due + _config.coyote_time && heading_for_wall();
+
+      // Once the snake has turned this move, a late turn away goes in line behind that one, so
+      // it's taken now rather than after the snake has already hit the wall.
+      while (held && movement_seconds >= due && !_controller && human.waiting()) {
+        if (const auto direct = human.next_direction(_snake, _grid)) {
+          turn(*direct);
+        }
+
+        held = heading_for_wall();
+      }
+
+      if (movement_seconds >= due && !held) {
+        step();
+
+        movement_seconds = 0.0f;
+      }
+
+      if ((_config.fruit_lifetime || _config.bonus_chance) && _state == GameStates::InProgress) {
+        age_fruit(secs);
+      }
+
+      break;
+    }
+    case GameStates::End:
+    case GameStates::Won:
+      end_seconds += secs;
+      break;
+    case GameStates::Paused:
+      break;
+    }
+  }
+
+  // Seconds from one move to the next, for now.
+  float move_due() const noexcept {
+    return slow_motion_seconds > slow_motion_cooldown ? interval * slow_motion_factor : interval;
+  }
+
+  // Draws the head and the tail a move behind, `progress` of the way from the blocks they were on
+  // to the ones they're on now, so the snake slides rather than jumping a block at a time. Anything
+  // that took the snake further than a block, like wrapping around, is drawn as it is.
+  void draw_sliding(sf::RenderTarget &target, sf::Color background, float progress) const {
+    const auto cells = _snake.cells();
+    const auto next_to = [](sf::Vector2u from, sf::Vector2u to) {
+      const auto dx = std::abs(int(from.x) - int(to.x));
+      const auto dy = std::abs(int(from.y) - int(to.y));
+
+      return from != to && dx <= 1 && dy <= 1;
+    };
+
+    if (moved_from.empty() || progress >= 1.0f || !next_to(moved_from.front(), cells.front()))
+      return;
+
+    const auto corner = [&](sf::Vector2u pos) {
+      return _grid.origin() + sf::Vector2f(pos) * block_len;
+    };
+    const auto slide = [&](sf::Vector2u from, sf::Vector2u to) {
+      sf::RectangleShape square(sf::Vector2f(block_len, block_len));
+      square.setPosition(corner(from) + (corner(to) - corner(from)) * progress);
+      square.setFillColor(_grid[to].colour());
+
+      target.draw(square);
+    };
+
+    // The head isn't in its block until it's slid all the way in. Inset, so the lines around the
+    // block stay.
+    const auto head = cells.front();
+    sf::RectangleShape cover(sf::Vector2f(block_len - 2.0f, block_len - 2.0f));
+    cover.setPosition(corner(head) + sf::Vector2f(1.0f, 1.0f));
+    cover.setFillColor(background);
+    target.draw(cover);
+
+    if (_grid.checkerboard() && (head.x + head.y) % 2 == 1) {
+      cover.setFillColor(theme::current().checker);
+      target.draw(cover);
+    }
+
+    // A tail that's where it was grew this move.
+    if (next_to(moved_from.back(), cells.back())) {
+      slide(moved_from.back(), cells.back());
+    }
+
+    slide(moved_from.front(), head);
+  }
+
+  // Each segment's place in the body, the head being 0, in the middle of its block.
+  void draw_segment_numbers(sf::RenderTarget &target, const sf::Font &font) const {
+    const auto cells = _snake.cells();
+
+    for (std::size_t i = 0; i < cells.size(); i++) {
+      sf::Text text(std::to_string(i), font, 10);
+      const auto bounds = text.getLocalBounds();
+
+      text.setOrigin(bounds.left + bounds.width / 2.0f, bounds.top + bounds.height / 2.0f);
+      text.setPosition(_grid[cells[i]].centre());
+      text.setFillColor(theme::current().background);
+
+      target.draw(text);
+    }
+  }
+
+  // In the top left corner of the grid.
+  void draw_score(sf::RenderTarget &target, const sf::Font &font) const {
+    // Points and fruit differ once there's been a bonus.
+    sf::Text text("score: " + std::to_string(_score) + " (" + std::to_string(fruit_eaten) +
+                    " fruit)",
+                  font, 16);
+    text.setPosition(_grid.origin() + sf::Vector2f(4.0f, 4.0f));
+    // A fixed colour would vanish against one theme's background or another's.
+    text.setFillColor(colour::contrasting(theme::current().background));
+
+    target.draw(text);
+  }
+
+  // Each popup rises and fades out over `popup_duration`.
+  void draw_popups(sf::RenderTarget &target, const sf::Font &font) const {
+    for (const auto &popup : popups) {
+      const auto age = (elapsed - popup.shown_at) / popup_duration;
+
+      sf::Text text(popup.label, font, 14);
+      const auto bounds = text.getLocalBounds();
+
+      text.setOrigin(bounds.left + bounds.width / 2.0f, bounds.top + bounds.height / 2.0f);
+      text.setPosition(popup.pos - sf::Vector2f(0.0f, popup_rise * age));
+
+      auto colour = theme::current().snake;
+      colour.a = std::uint8_t(255.0f * std::clamp(1.0f - age, 0.0f, 1.0f));
+      text.setFillColor(colour);
+
+      target.draw(text);
+    }
+  }
+
+  // Fills the meter's columns from the bottom up, left to right, with a square per segment of the
+  // body, so not the head. Whatever doesn't fit is left out.
+  void draw_length_meter(sf::RenderTarget &target) const {
+    static constexpr float square = 8.0f;
+    static constexpr float gap = 2.0f;
+
+    const auto left = _grid.origin().x + float(_grid.horizontal()) * block_len + block_len / 2.0f;
+    const auto bottom = _grid.origin().y + float(_grid.vertical()) * block_len;
+
+    const auto rows = std::size_t(float(_grid.vertical()) * block_len / (square + gap));
+    const auto columns = std::size_t((length_meter_width - block_len / 2.0f) / (square + gap));
+
+    sf::RectangleShape segment(sf::Vector2f(square, square));
+    segment.setFillColor(theme::current().snake);
+
+    const auto body = _snake.len() - 1;
+
+    for (std::size_t i = 0; i < std::min(body, rows * columns); i++) {
+      segment.setPosition(left + float(i / rows) * (square + gap),
+                          bottom - float(i % rows + 1) * (square + gap));
+      target.draw(segment);
+    }
+  }
+
+  // One small square per block that isn't vacant, on a translucent panel in the bottom-right
+  // corner of the view, or the bottom-left one while the head is under it.
+  void draw_minimap(sf::RenderTarget &target) const {
+    static constexpr float margin = 4.0f;
+
+    const auto &view = target.getView();
+    const auto top_left = view.getCenter() - view.getSize() / 2.0f;
+
+    const auto scale =
+      std::min({minimap_scale, view.getSize().x * minimap_share / float(_grid.horizontal()),
+                view.getSize().y * minimap_share / float(_grid.vertical())});
+    const auto size = sf::Vector2f(float(_grid.horizontal()), float(_grid.vertical())) * scale;
+
+    auto corner = top_left + view.getSize() - size - sf::Vector2f(margin, margin);
+
+    const auto head = _grid.origin() + sf::Vector2f(_snake.head()) * block_len;
+    if (head.x + block_len >= corner.x && head.y + block_len >= corner.y) {
+      corner.x = top_left.x + margin;
+    }
+
+    sf::RectangleShape panel(size);
+    panel.setPosition(corner);
+    panel.setFillColor(minimap_background);
+    target.draw(panel);
+
+    sf::VertexArray cells(sf::Quads);
+
+    for (std::size_t i = 0; i < _grid.len(); i++) {
+      const auto &block = _grid[i];
+
+      if (block.type() == BlockType::Vacant)
+        continue;
+
+      const auto colour = block.type() == BlockType::OccupiedSnake ? theme::current().snake
+                          : block.type() == BlockType::Wall        ? theme::current().wall
+                                                                   : block.colour();
+      const auto pos = corner + sf::Vector2f(_grid.to_xy(i)) * scale;
+
+      cells.append(sf::Vertex(pos, colour));
+      cells.append(sf::Vertex(pos + sf::Vector2f(scale, 0.0f), colour));
+      cells.append(sf::Vertex(pos + sf::Vector2f(scale, scale), colour));
+      cells.append(sf::Vertex(pos + sf::Vector2f(0.0f, scale), colour));
+    }
+
+    target.draw(cells);
+  }
+
+  // Flashes the cell the snake died on; off the grid, the nearest cell on it.
+  void draw_death_marker(sf::RenderTarget &target) const {
+    if (!death || end_seconds >= death_flash_duration)
+      return;
+
+    const auto x = std::clamp(death->x, 0, int(_grid.horizontal()) - 1);
+    const auto y = std::clamp(death->y, 0, int(_grid.vertical()) - 1);
+
+    sf::RectangleShape marker(sf::Vector2f(block_len, block_len));
+    marker.setPosition(_grid.origin() + sf::Vector2f(float(x) * block_len, float(y) * block_len));
+    marker.setFillColor(death_colours[std::size_t(end_seconds / death_flash_period) % 2]);
+
+    target.draw(marker);
+  }
+
+public:
+  // A `demo` starts out on the title screen instead, with the autopilot playing.
+  Game(const Config &config, sf::Vector2u resolution, bool demo = false)
+    : _config(config), _resolution(resolution),
+      _grid(make_grid(config, resolution)),
+      _arena(config.zen ? std::optional<Arena>(std::in_place, _grid, config.zen_size)
+                        : std::nullopt),
+      _snake(_grid, _config), _editor(_grid, _snake), _ghost(_grid.origin()), _demo(demo) {
+    _grid.set_show_vacant(config.grid_lines);
+    _grid.set_checkerboard(config.checkerboard);
+    _grid.set_round_fruit(config.round_fruit);
+
+    if (config.speed_curve) {
+      interval = curve_interval(0, config);
+    } else if (config.length_speedup) {
+      interval = length_interval(_snake.len(), config);
+    }
+
+    if (demo) {
+      _state = GameStates::Title;
+      _controller = std::make_unique<AiController>();
+
+      for (std::size_t i = 0; i < _config.initial_fruits && _grid.free_count() > 0; i++) {
+        spawn_fruit(_grid, _snake, _config, *_spawner);
+      }
+
+      return;
+    }
+
+    if (_config.keep_records) {
+      _ghost.load(record_path(ghost_path));
+    }
+
+    if (config.setup) {
+      _state = GameStates::Setup;
+    } else if (config.editor) {
+      _state = GameStates::Editor;
+      _message = "editor (S snake, F fruit, Ctrl+Z undo, Ctrl+S save, Enter play)";
+    }
+  }
+
+  // Everything above refers to `_grid`, so a game stays where it was made.
+  Game(const Game &) = delete;
+  Game &operator=(const Game &) = delete;
+
+  // Called with every `GameEvent` as it happens, demos included. Replaces any earlier observer.
+  void set_observer(std::function<void(const GameEvent &)> callback) {
+    observer = std::move(callback);
+  }
+
+  GameStates state() const noexcept { return _state; }
+
+  void set_minimap(bool show) noexcept { _config.minimap = show; }
+
+  bool demo() const noexcept { return _demo; }
+
+  // Hands the steering to `controller`, or back to the player with none.
+  void set_controller(std::unique_ptr<Controller> controller) {
+    _controller = std::move(controller);
+  }
+
+  // Set by the same move that ends the game, so it holds from the first frame of game over.
+  bool over() const noexcept { return _state == GameStates::End || _state == GameStates::Won; }
+
+  // Whether a key may start another game, or quit, instead of steering. That's straight away once
+  // the game is over, however it ended, with nothing like the death marker to wait for.
+  bool can_restart() const noexcept { return over(); }
+
+  Grid &grid() noexcept { return _grid; }
+
+  const Grid &grid() const noexcept { return _grid; }
+
+  const Snake &snake() const noexcept { return _snake; }
+
+  Editor &editor() noexcept { return _editor; }
+
+  std::size_t step_count() const noexcept { return steps; }
+
+  std::size_t score() const noexcept { return _score; }
+
+  // How many fruit have been eaten, whatever each was worth.
+  std::size_t eaten_count() const noexcept { return fruit_eaten; }
+
+  std::size_t level() const noexcept { return _level; }
+
+  // Every game starts at level 1 and goes up one every `Config::level_fruits` points.
+  std::size_t level_for_score(std::size_t score) const noexcept {
+    return 1 + score / _config.level_fruits;
+  }
+
+  // How far along the way to the next level the score is, from 0 up to but not including 1.
+  float level_progress() const noexcept {
+    return float(_score % _config.level_fruits) / float(_config.level_fruits);
+  }
+
+  std::string window_title() const {
+    auto text = std::string(title);
+
+    if (_config.levels) {
+      text += " - level " + std::to_string(_level);
+    }
+
+    // The overlay says so too, but only with a font.
+    if (_state == GameStates::Paused) {
+      text += " - paused";
+    }
+
+    return _message.empty() ? text : text + " : " + _message;
+  }
+
+  void set_message(std::string message) { _message = std::move(message); }
+
+  // Leaves the editor for the start of the game.
+  void start() {
+    if (_state != GameStates::Editor)
+      return;
+
+    _state = GameStates::Start;
+    _message.clear();
+  }
+
+  void toggle_pause() noexcept {
+    if (_state == GameStates::InProgress) {
+      _state = GameStates::Paused;
+    } else if (_state == GameStates::Paused) {
+      _state = GameStates::InProgress;
+    }
+
+    idle_paused = false;
+  }
+
+  // Carries on from a pause after `Config::idle_pause` ran out. Returns false, doing nothing, for
+  // any other pause, so the key that woke the game up isn't taken for anything else.
+  bool wake() noexcept {
+    if (!idle_paused || _state != GameStates::Paused)
+      return false;
+
+    toggle_pause();
+    _message.clear();
+    idle_seconds = 0.0f;
+
+    return true;
+  }
+
+  // Takes the last move back in a practice game, even one that ended it, and pauses so the player
+  // can pick another way to go. Returns false if there's nothing to take back.
+  bool undo() {
+    if (rewind.empty() || (_state != GameStates::InProgress && _state != GameStates::Paused &&
+                           _state != GameStates::End))
+      return false;
+
+    auto snapshot = std::move(rewind.back());
+    rewind.pop_back();
+
+    _score = load_json(snapshot.board, _grid, _snake, _resolution);
+    // The board keeps the colours it was saved with, and the theme may have changed since.
+    _grid.apply_theme(theme::current());
+
+    steps = snapshot.steps;
+    fruit_eaten = snapshot.fruit_eaten;
+    _level = snapshot.level;
+    interval = snapshot.interval;
+    reverse_seconds = snapshot.reverse_seconds;
+    freeze_seconds = snapshot.freeze_seconds;
+    _spawner->set_position(snapshot.spawner_position);
+    fruit_ages = std::move(snapshot.fruit_ages);
+
+    turned = false;
+    human.clear();
+    movement_seconds = 0.0f;
+    moved_from.clear();
+    death.reset();
+    end_seconds = 0.0f;
+
+    _state = GameStates::Paused;
+    idle_paused = false;
+    _message = "rewound, " + std::to_string(rewind.size()) + " left";
+
+    return true;
+  }
+
+  // Ends a run that is still going when the window is closed, so it's saved like any other.
+  void quit() {
+    if (_state == GameStates::InProgress || _state == GameStates::Paused) {
+      end("quit", Ending::Quit);
+    }
+  }
+
+  // Steers the snake. Ignored while paused, and inverted while a reverse fruit is in effect.
+  void input(Direction direct) {
+    if (_state == GameStates::Paused || _state == GameStates::Editor ||
+        _state == GameStates::Title || _state == GameStates::Setup || direct == Direction::None)
+      return;
+    if (is_diagonal(direct) && !_config.diagonal)
+      return;
+
+    idle_seconds = 0.0f;
+
+    if (reverse_seconds > 0.0f) {
+      direct = opposite(direct);
+    }
+
+    // Anything already waiting goes first.
+    if (!turned && !human.waiting()) {
+      turn(direct);
+      return;
+    }
+
+    // Checked against the turn before it in line, so a held key's repeats and turns straight back,
+    // which would only be refused once taken, don't fill up the buffer. Just like
+    // `Snake::set_direction`, that goes for a snake with no body too.
+    const auto last = human.last().value_or(_snake.direction());
+
+    if (direct != last && direct != opposite(last)) {
+      human.push(direct);
+    }
+  }
+
+  bool export_state(const std::string &path) {
+    std::ofstream file(path);
+    file << to_json(_snake, _grid, _score);
+
+    _message = (file ? "exported " : "could not export ") + path;
+    return bool(file);
+  }
+
+  // Loading mid-game would be hard to follow, so it's only allowed before the first move.
+  bool import_state(const std::string &path) {
+    if (_state != GameStates::Start)
+      return false;
+
+    try {
+      std::ifstream file(path);
+      if (!file) {
+        throw std::runtime_error("could not open " + path);
+      }
+
+      std::ostringstream text;
+      text << file.rdbuf();
+
+      _score = load_json(text.str(), _grid, _snake, _resolution);
+      // Any walls the levels put up are already on the board, but the speed starts over.
+      _level = level_for_score(_score);
+      _message = "imported " + path;
+      return true;
+    } catch (std::runtime_error const &ex) {
+      _message = ex.what();
+      return false;
+    }
+  }
+
+  // Replaces the board with `scenario`, which like an import can only happen before the first move.
+  // With a direction given, the snake sets off straight away.
+  bool load_scenario(const Scenario &scenario) {
+    if (_state != GameStates::Start)
+      return false;
+
+    Grid loaded(scenario.horizontal, scenario.vertical, _grid.origin(), _resolution);
+    loaded.set_show_vacant(_grid.show_vacant());
+    loaded.set_checkerboard(_grid.checkerboard());
+    loaded.set_round_fruit(_grid.round_fruit());
+
+    for (std::size_t i = 0; i < loaded.len(); i++) {
+      if (scenario.blocks[i] == BlockType::OccupiedFruit) {
+        loaded.place_fruit(i);
+        continue;
+      }
+
+      loaded.set_type(i, scenario.blocks[i]);
+
+      if (scenario.blocks[i] == BlockType::Wall) {
+        loaded.set_colour(i, theme::current().wall);
+      }
+    }
+
+    loaded.set_type(scenario.head, BlockType::OccupiedSnake);
+    loaded.set_colour(scenario.head, theme::current().snake);
+    for (const auto pos : scenario.body) {
+      loaded.set_type(pos, BlockType::OccupiedSnake);
+      loaded.set_colour(pos, theme::current().snake);
+    }
+
+    _grid = std::move(loaded);
+    _snake.restore(scenario.head, scenario.body, scenario.direction);
+    scripted = true;
+
+    return true;
+  }
+
+  // Lays the setup screen's preview out again at the size `config` now asks for, in a window of
+  // `resolution`. The walls are kept and the snake starts over wherever it would at that size.
+  // Zen's arena is measured from the grid it was made with, so a zen game has to be made again.
+  bool resize(const Config &config, sf::Vector2u resolution) {
+    if (_state != GameStates::Setup || _arena)
+      return false;
+
+    _config.horizontal = config.horizontal;
+    _config.vertical = config.vertical;
+    _resolution = resolution;
+
+    _grid.resize(_config.horizontal, _config.vertical, origin_for(_config, resolution), resolution);
+
+    // Any the grid used to be too small for.
+    for (const auto pos : _config.walls) {
+      if (pos.x >= _grid.horizontal() || pos.y >= _grid.vertical())
+        continue;
+      if (_grid[pos].type() == BlockType::OccupiedSnake)
+        continue;
+
+      _grid.set_type(pos, BlockType::Wall);
+      _grid.set_colour(pos, theme::current().wall);
+    }
+
+    _snake.respawn();
+
+    return true;
+  }
+
+  // Draws the board as `render` would into an image at `path`.
+  bool save_image(const std::string &path, const sf::Font *font) const {
+    sf::RenderTexture texture;
+    if (!texture.create(_resolution.x, _resolution.y))
+      return false;
+
+    render(texture, font);
+    texture.display();
+
+    return texture.getTexture().copyToImage().saveToFile(path);
+  }
+
+  // Moves the game `secs` seconds forward.
+  void advance(float secs) {
+    elapsed += secs;
+
+    popups.erase(std::remove_if(popups.begin(), popups.end(),
+                                [&](const Popup &popup) {
+                                  return elapsed - popup.shown_at >= popup_duration;
+                                }),
+                 popups.end());
+
+    update(secs);
+
+    // After moving, which paints the head green again.
+    if (_config.rainbow) {
+      const auto colour = colour::from_hsv(elapsed * rainbow_speed, 1.0f, 1.0f);
+
+      for (const auto pos : _snake.cells()) {
+        _grid.set_colour(pos, colour);
+      }
+    }
+  }
+
+  // How far the snake is through its move, from 0 just after the last one to 1 when the next is
+  // due, counting `lag` seconds that have passed but haven't been played yet.
+  float move_progress(float lag = 0.0f) const noexcept {
+    const auto seconds = freeze_seconds > 0.0f ? movement_seconds : movement_seconds + lag;
+
+    return std::clamp(seconds / move_due(), 0.0f, 1.0f);
+  }
+
+  // Text is left out without a font. `lag` is how far the clock is past the last `advance`, for
+  // `Config::interpolate`.
+  void render(sf::RenderTarget &target, const sf::Font *font, float lag = 0.0f) const {
+    const auto &colours = theme::current();
+    auto background = colours.background;
+
+    if (_config.eat_flash && eaten_at) {
+      const auto fade = (elapsed - *eaten_at) / eat_flash_duration;
+      background = colour::lerp(colours.flash, colours.background, fade);
+    }
+
+    target.clear(background);
+    target.draw(_grid);
+
+    if (_config.interpolate &&
+        (_state == GameStates::InProgress || _state == GameStates::Title)) {
+      draw_sliding(target, background, move_progress(lag));
+    }
+
+    if (_state == GameStates::InProgress || _state == GameStates::Paused) {
+      target.draw(_ghost);
+    }
+
+    if (_config.segment_numbers && font) {
+      draw_segment_numbers(target, *font);
+    }
+
+    if (font) {
+      draw_popups(target, *font);
+    }
+
+    // Not behind the demo, which isn't anyone's score.
+    if (font && _state != GameStates::Title && _state != GameStates::Setup) {
+      draw_score(target, *font);
+    }
+
+    if (_config.length_meter) {
+      draw_length_meter(target);
+    }
+
+    if (_config.minimap) {
+      draw_minimap(target);
+    }
+
+    if (_config.progress_bar) {
+      // Fits in the margin above the grid.
+      const auto width = float(_grid.horizontal()) * block_len;
+
+      sf::RectangleShape bar(sf::Vector2f(width * level_progress(), _grid.origin().y / 2.0f));
+      bar.setPosition(_grid.origin().x, _grid.origin().y / 4.0f);
+      bar.setFillColor(progress_colour);
+
+      target.draw(bar);
+    }
+
+    if (_state == GameStates::Paused) {
+      draw_overlay(target, font, "PAUSED");
+    } else if (_state == GameStates::End) {
+      draw_overlay(target, font,
+                   "GAME OVER\n\npress R or Enter to play again\nor Escape to quit", 20);
+    } else if (_state == GameStates::Won) {
+      draw_overlay(target, font, "YOU WIN\n\npress R or Enter to play again\nor Escape to quit",
+                   20);
+    } else if (_state == GameStates::Setup) {
+      draw_overlay(target, font,
+                   std::to_string(_grid.horizontal()) + " x " + std::to_string(_grid.vertical()) +
+                     "\n\nleft/right or -/+ change the width\nup/down change the height\n\n"
+                     "press Enter to play",
+                   20);
+    } else if (_state == GameStates::Title) {
+      std::string label = "SNEK\n\narrow keys steer, space pauses\n\npress any key to start";
+
+      if (_lifetime.games > 0) {
+        label += "\n\n" + _lifetime.summary();
+      }
+
+      draw_overlay(target, font, label, 20);
+    }
+
+    // On top of the overlay, so it stands out.
+    if (_state == GameStates::End && _config.death_marker) {
+      draw_death_marker(target);
+    }
+  }
+};
@@ -0,0 +1,552 @@
+#pragma once
+
+#include "theme.hpp"
+
+#include <SFML/Graphics.hpp>
+#include <SFML/System/Vector2.hpp>
+#include <algorithm>
+#include <cmath>
+#include <cstddef>
+#include <cstdint>
+#include <optional>
+#include <stdexcept>
+#include <unordered_set>
+#include <utility>
+#include <vector>
+
+static constexpr float block_len = 25.0f;
+
+// Round fruit is drawn as a fan of this many triangles.
+static constexpr std::size_t fruit_segments = 16;
+
+enum class BlockType : std::uint8_t {
+  Vacant,
+  OccupiedSnake,
+  OccupiedFruit,
+  Wall,
+  // Left behind by the snake in paint mode. Looks different, but behaves just like `Vacant`.
+  Painted,
+};
+
+// What happens when the snake eats a fruit, on top of growing.
+enum class FruitKind : std::uint8_t {
+  Normal,
+  // Swaps left with right and up with down for a few seconds.
+  Reverse,
+  // Stops the snake for a couple of seconds, giving the player time to pick the next direction.
+  Freeze,
+  // Rare, short-lived and worth a lot more than the rest.
+  Bonus,
+};
+
+inline constexpr bool is_occupied(BlockType type) {
+  return type == BlockType::OccupiedFruit || type == BlockType::OccupiedSnake ||
+         type == BlockType::Wall;
+}
+
+inline sf::Vector2u operator+(sf::Vector2u lhs, sf::Vector2i rhs) {
+  return sf::Vector2u(uint(int(lhs.x) + rhs.x), uint(int(lhs.y) + rhs.y));
+}
+
+enum class Direction : uint8_t {
+  None,
+  Left,
+  Right,
+  Up,
+  Down,
+  // Only with `Config::diagonal`.
+  UpLeft,
+  UpRight,
+  DownLeft,
+  DownRight,
+};
+
+// The four straight directions; the diagonals aren't included.
+static constexpr Direction all_directions[] = {
+  Direction::Left,
+  Direction::Right,
+  Direction::Up,
+  Direction::Down,
+};
+
+inline sf::Vector2i to_pos(Direction direction) {
+  int x = 0;
+  int y = 0;
+
+  switch (direction) {
+  case Direction::Left:
+    x = -1;
+    break;
+  case Direction::Right:
+    x = 1;
+    break;
+  case Direction::Up:
+    y = -1;
+    break;
+  case Direction::Down:
+    y = 1;
+    break;
+  case Direction::UpLeft:
+    x = -1;
+    y = -1;
+    break;
+  case Direction::UpRight:
+    x = 1;
+    y = -1;
+    break;
+  case Direction::DownLeft:
+    x = -1;
+    y = 1;
+    break;
+  case Direction::DownRight:
+    x = 1;
+    y = 1;
+    break;
+  default:
+    break;
+  }
+
+  return sf::Vector2i(x, y);
+}
+
+inline Direction opposite(Direction direction) {
+  switch (direction) {
+  case Direction::Left:
+    return Direction::Right;
+  case Direction::Right:
+    return Direction::Left;
+  case Direction::Up:
+    return Direction::Down;
+  case Direction::Down:
+    return Direction::Up;
+  case Direction::UpLeft:
+    return Direction::DownRight;
+  case Direction::UpRight:
+    return Direction::DownLeft;
+  case Direction::DownLeft:
+    return Direction::UpRight;
+  case Direction::DownRight:
+    return Direction::UpLeft;
+  default:
+    return Direction::None;
+  }
+}
+
+// Halfway between `vertical` (up or down) and `horizontal` (left or right), or `Direction::None` if
+// they aren't one of each.
+inline Direction diagonal(Direction vertical, Direction horizontal) {
+  if (vertical == Direction::Up && horizontal == Direction::Left)
+    return Direction::UpLeft;
+  if (vertical == Direction::Up && horizontal == Direction::Right)
+    return Direction::UpRight;
+  if (vertical == Direction::Down && horizontal == Direction::Left)
+    return Direction::DownLeft;
+  if (vertical == Direction::Down && horizontal == Direction::Right)
+    return Direction::DownRight;
+
+  return Direction::None;
+}
+
+// The way `delta` points, going by the signs of its components alone.
+inline Direction direction_of(sf::Vector2i delta) {
+  const auto horizontal = delta.x < 0   ? Direction::Left
+                          : delta.x > 0 ? Direction::Right
+                                        : Direction::None;
+  const auto vertical = delta.y < 0   ? Direction::Up
+                        : delta.y > 0 ? Direction::Down
+                                      : Direction::None;
+
+  if (vertical == Direction::None)
+    return horizontal;
+  if (horizontal == Direction::None)
+    return vertical;
+
+  return diagonal(vertical, horizontal);
+}
+
+inline bool is_diagonal(Direction direction) {
+  return direction == Direction::UpLeft || direction == Direction::UpRight ||
+         direction == Direction::DownLeft || direction == Direction::DownRight;
+}
+
+class Block : public sf::Drawable {
+  BlockType m_type = BlockType::Vacant;
+  FruitKind m_fruit = FruitKind::Normal;
+  sf::Color m_colour = theme::current().lines;
+  // Whether fruit is drawn as a circle instead of a quad.
+  bool m_round = true;
+
+  sf::VertexArray m_arr;
+  sf::VertexArray m_fan;
+
+public:
+  Block() noexcept
+  : m_type(BlockType::Vacant), m_colour(theme::current().lines), m_arr(sf::LinesStrip, 5),
+    m_fan(sf::TriangleFan, fruit_segments + 2) {
+    set_colour(m_colour);
+  }
+
+  sf::Vector2f position() const noexcept { return m_arr[0].position; }
+  sf::Vector2f centre() const noexcept {
+    return position() + sf::Vector2f(block_len / 2.0f, block_len / 2.0f);
+  }
+  BlockType type() const noexcept { return m_type; }
+  FruitKind fruit() const noexcept { return m_fruit; }
+  sf::Color colour() const noexcept { return m_colour; }
+
+  void set_fruit(FruitKind fruit) noexcept { m_fruit = fruit; }
+  void set_round(bool round) noexcept { m_round = round; }
+
+  void set_position(sf::Vector2f pos) noexcept {
+    m_arr[0].position = pos;
+    m_arr[1].position = sf::Vector2f(pos.x + block_len, pos.y);
+    m_arr[2].position = pos + sf::Vector2f(block_len, block_len);
+    m_arr[3].position = sf::Vector2f(pos.x, pos.y + block_len);
+    m_arr[4].position = pos;
+
+    constexpr float pi = 3.14159265f;
+    const float radius = block_len / 2.0f;
+    const auto centre = pos + sf::Vector2f(radius, radius);
+
+    m_fan[0].position = centre;
+    for (std::size_t i = 0; i <= fruit_segments; i++) {
+      const float angle = 2.0f * pi * float(i) / float(fruit_segments);
+      m_fan[i + 1].position =
+        centre + sf::Vector2f(radius * std::cos(angle), radius * std::sin(angle));
+    }
+  }
+
+  void set_colour(sf::Color colour) noexcept {
+    m_colour = colour;
+
+    for (std::size_t i = 0; i < m_arr.getVertexCount(); i++) {
+      m_arr[i].color = colour;
+    }
+
+    for (std::size_t i = 0; i < m_fan.getVertexCount(); i++) {
+      m_fan[i].color = colour;
+    }
+  }
+
+  void set_type(BlockType type) noexcept {
+    switch (type) {
+    case BlockType::Vacant:
+      m_arr.setPrimitiveType(sf::LinesStrip);
+      break;
+    case BlockType::OccupiedSnake:
+    case BlockType::OccupiedFruit:
+    case BlockType::Wall:
+    case BlockType::Painted:
+      m_arr.setPrimitiveType(sf::Quads);
+      break;
+    }
+
+    m_type = type;
+  }
+
+  void draw(sf::RenderTarget &target, sf::RenderStates states) const override {
+    if (m_round && m_type == BlockType::OccupiedFruit) {
+      target.draw(m_fan, states);
+    } else {
+      target.draw(m_arr, states);
+    }
+  }
+};
+
+class Grid : public sf::Drawable {
+  std::size_t _horizontal, _vertical;
+  sf::Vector2f _origin;
+  std::vector<Block> blocks;
+
+  // Maintained by `set_type` so that the occupancy queries don't have to scan `blocks`.
+  std::size_t _occupied = 0;
+  std::size_t _fruit = 0;
+
+  // When vacant blocks are hidden, only this outline of the whole grid is drawn for them.
+  bool _show_vacant = true;
+  sf::VertexArray _border;
+
+  // Every other block can be tinted ever so slightly, beneath everything else.
+  bool _checkerboard = false;
+  sf::VertexArray _checker;
+
+  bool _round_fruit = true;
+
+public:
+  Grid(std::size_t horizontal, std::size_t vertical, sf::Vector2f pos, sf::Vector2u resolution)
+    : _horizontal(horizontal), _vertical(vertical), _origin(pos), blocks(horizontal * vertical),
+      _border(sf::LinesStrip, 5), _checker(sf::Quads) {
+    const auto extent = sf::Vector2f(float(horizontal) * block_len, float(vertical) * block_len);
+
+    _border[0].position = pos;
+    _border[1].position = pos + sf::Vector2f(extent.x, 0.0f);
+    _border[2].position = pos + extent;
+    _border[3].position = pos + sf::Vector2f(0.0f, extent.y);
+    _border[4].position = pos;
+
+    for (std::size_t i = 0; i < _border.getVertexCount(); i++) {
+      _border[i].color = theme::current().lines;
+    }
+
+    // None at all in a window too small to reach past the origin.
+    const auto max_blocks_horizontal =
+      std::size_t(std::floor(std::max(float(resolution.x) - pos.x, 0.0f) / block_len));
+    const auto max_blocks_vertical =
+      std::size_t(std::floor(std::max(float(resolution.y) - pos.y, 0.0f) / block_len));
+
+    const float first_x = pos.x;
+
+    for (std::size_t x = 0, y = 0; y < std::min(max_blocks_vertical, vertical); y++) {
+      for (; x < std::min(max_blocks_horizontal, horizontal); x++) {
+        auto& block = blocks[x + y * horizontal];
+        block.set_position(pos);
+
+        pos.x += block_len;
+      }
+
+      x = 0;
+      pos.x = first_x;
+      pos.y += block_len;
+    }
+
+    const auto tint = theme::current().checker;
+
+    for (std::size_t i = 0; i < blocks.size(); i++) {
+      const auto xy = to_xy(i);
+      if ((xy.x + xy.y) % 2 == 0)
+        continue;
+
+      const auto corner = blocks[i].position();
+
+      _checker.append(sf::Vertex(corner, tint));
+      _checker.append(sf::Vertex(corner + sf::Vector2f(block_len, 0.0f), tint));
+      _checker.append(sf::Vertex(corner + sf::Vector2f(block_len, block_len), tint));
+      _checker.append(sf::Vertex(corner + sf::Vector2f(0.0f, block_len), tint));
+    }
+  }
+
+  // Lays the grid out again `horizontal` by `vertical` blocks from `pos`, keeping every block that
+  // is still inside it as it was. Anything that was only in the part cut off is gone, so whatever
+  // keeps track of the snake or fruit there has to deal with that itself.
+  void resize(std::size_t horizontal, std::size_t vertical, sf::Vector2f pos,
+              sf::Vector2u resolution) {
+    Grid resized(horizontal, vertical, pos, resolution);
+    resized.set_show_vacant(_show_vacant);
+    resized.set_checkerboard(_checkerboard);
+    resized.set_round_fruit(_round_fruit);
+
+    for (std::size_t y = 0; y < std::min(vertical, _vertical); y++) {
+      for (std::size_t x = 0; x < std::min(horizontal, _horizontal); x++) {
+        const auto &block = blocks[x + y * _horizontal];
+        const auto i = x + y * horizontal;
+
+        resized.set_type(i, block.type());
+        resized.set_fruit(i, block.fruit());
+        resized.set_colour(i, block.colour());
+      }
+    }
+
+    *this = std::move(resized);
+  }
+
+  void draw(sf::RenderTarget &target, sf::RenderStates states) const override {
+    if (_checkerboard) {
+      target.draw(_checker, states);
+    }
+
+    if (!_show_vacant) {
+      target.draw(_border, states);
+    }
+
+    for (const auto &block : blocks) {
+      if (_show_vacant || block.type() != BlockType::Vacant) {
+        target.draw(block, states);
+      }
+    }
+  }
+
+  // Turns every painted block back into a vacant one.
+  void clear_paint() noexcept {
+    for (auto &block : blocks) {
+      if (block.type() == BlockType::Painted) {
+        block.set_type(BlockType::Vacant);
+        block.set_colour(theme::current().lines);
+      }
+    }
+  }
+
+  // Recolours the border, the checkerboard, vacant blocks, the snake and walls. Fruit and paint
+  // keep their colours.
+  void apply_theme(const Theme &theme) noexcept {
+    for (std::size_t i = 0; i < _border.getVertexCount(); i++) {
+      _border[i].color = theme.lines;
+    }
+
+    for (std::size_t i = 0; i < _checker.getVertexCount(); i++) {
+      _checker[i].color = theme.checker;
+    }
+
+    for (auto &block : blocks) {
+      switch (block.type()) {
+      case BlockType::Vacant:
+        block.set_colour(theme.lines);
+        break;
+      case BlockType::OccupiedSnake:
+        block.set_colour(theme.snake);
+        break;
+      case BlockType::Wall:
+        block.set_colour(theme.wall);
+        break;
+      case BlockType::OccupiedFruit:
+      case BlockType::Painted:
+        break;
+      }
+    }
+  }
+
+  bool show_vacant() const noexcept { return _show_vacant; }
+  void set_show_vacant(bool show) noexcept { _show_vacant = show; }
+
+  bool checkerboard() const noexcept { return _checkerboard; }
+  void set_checkerboard(bool checkerboard) noexcept { _checkerboard = checkerboard; }
+
+  bool round_fruit() const noexcept { return _round_fruit; }
+  void set_round_fruit(bool round) noexcept {
+    _round_fruit = round;
+
+    for (auto &block : blocks) {
+      block.set_round(round);
+    }
+  }
+
+  std::size_t horizontal() const noexcept { return _horizontal; }
+  std::size_t vertical() const noexcept { return _vertical; }
+  std::size_t len() const noexcept { return blocks.size(); }
+  sf::Vector2f origin() const noexcept { return _origin; }
+
+  sf::Vector2u to_xy(std::size_t pos) const noexcept {
+    return sf::Vector2u(pos % horizontal(), pos / horizontal());
+  }
+
+  std::size_t to_index(sf::Vector2u pos) const noexcept { return pos.x + pos.y * horizontal(); }
+
+  // The block next to `pos` in `direct`. With `wrap`, going off one edge of the grid comes back in
+  // on the opposite edge; otherwise there is no neighbour past the edge.
+  std::optional<std::size_t> neighbour(std::size_t pos, Direction direct, bool wrap) const {
+    const auto xy = to_xy(pos);
+    const auto delta = to_pos(direct);
+
+    auto x = long(xy.x) + delta.x;
+    auto y = long(xy.y) + delta.y;
+
+    if (wrap) {
+      x = (x + long(horizontal())) % long(horizontal());
+      y = (y + long(vertical())) % long(vertical());
+    } else if (x < 0 || y < 0 || x >= long(horizontal()) || y >= long(vertical())) {
+      return std::nullopt;
+    }
+
+    return to_index(sf::Vector2u(unsigned(x), unsigned(y)));
+  }
+
+  // Every block that can be walked to from `start` without going through a wall.
+  std::unordered_set<std::size_t> reachable_from(std::size_t start) const;
+
+  std::size_t manhattan(std::size_t a, std::size_t b) const noexcept {
+    const auto lhs = to_xy(a);
+    const auto rhs = to_xy(b);
+
+    return std::size_t(std::abs(int(lhs.x) - int(rhs.x)) + std::abs(int(lhs.y) - int(rhs.y)));
+  }
+
+  // Inverse of the layout done in the constructor: the block covering the point, if any.
+  std::optional<std::size_t> cell_at_pixel(float px, float py) const noexcept {
+    if (px < _origin.x || py < _origin.y)
+      return std::nullopt;
+
+    const auto x = std::size_t((px - _origin.x) / block_len);
+    const auto y = std::size_t((py - _origin.y) / block_len);
+
+    if (x >= horizontal() || y >= vertical())
+      return std::nullopt;
+
+    return x + y * horizontal();
+  }
+
+  std::size_t occupied_count() const noexcept { return _occupied; }
+  std::size_t fruit_count() const noexcept { return _fruit; }
+
+  // Every block with a fruit on it, in index order.
+  std::vector<std::size_t> fruit_positions() const {
+    std::vector<std::size_t> fruit;
+    fruit.reserve(_fruit);
+
+    // The counter says how many there are, so stop looking once they've all been found.
+    for (std::size_t pos = 0; pos < len() && fruit.size() < _fruit; pos++) {
+      if (blocks[pos].type() == BlockType::OccupiedFruit)
+        fruit.push_back(pos);
+    }
+
+    return fruit;
+  }
+
+  std::size_t free_count() const noexcept { return len() - occupied_count(); }
+
+  // Fraction of the grid that is occupied, from 0 (empty) to 1 (full).
+  float fill_ratio() const noexcept { return float(occupied_count()) / float(len()); }
+
+  void set_type(std::size_t pos, BlockType type) noexcept {
+    auto &block = blocks[pos];
+
+    _occupied -= is_occupied(block.type());
+    _fruit -= block.type() == BlockType::OccupiedFruit;
+
+    block.set_type(type);
+
+    _occupied += is_occupied(type);
+    _fruit += type == BlockType::OccupiedFruit;
+  }
+
+  void set_type(sf::Vector2u pos, BlockType type) noexcept {
+    set_type(to_index(pos), type);
+  }
+
+  void set_fruit(std::size_t pos, FruitKind fruit) noexcept { blocks[pos].set_fruit(fruit); }
+
+  void set_colour(std::size_t pos, sf::Color colour) noexcept { blocks[pos].set_colour(colour); }
+
+  void set_colour(sf::Vector2u pos, sf::Color colour) noexcept {
+    set_colour(to_index(pos), colour);
+  }
+
+  // Puts an ordinary fruit at `pos` in the theme's first fruit colour, so nothing is left to the
+  // RNG. Returns false, leaving the block alone, if it's not free.
+  bool place_fruit(std::size_t pos) noexcept {
+    if (is_occupied(blocks[pos].type()))
+      return false;
+
+    set_type(pos, BlockType::OccupiedFruit);
+    set_fruit(pos, FruitKind::Normal);
+    set_colour(pos, theme::current().fruit.front());
+
+    return true;
+  }
+
+  // Moves the fruit at `from`, kind, colour and all, to `to`, which should be free.
+  void move_fruit(std::size_t from, std::size_t to) noexcept {
+    const auto fruit = blocks[from].fruit();
+    const auto colour = blocks[from].colour();
+
+    set_type(from, BlockType::Vacant);
+    set_colour(from, theme::current().lines);
+
+    set_type(to, BlockType::OccupiedFruit);
+    set_fruit(to, fruit);
+    set_colour(to, colour);
+  }
+
+  // Blocks are only handed out read-only; mutations go through `set_type` and `set_colour` so the
+  // grid can keep its bookkeeping in sync.
+  const Block &operator[](sf::Vector2u pos) const { return blocks[to_index(pos)]; }
+  const Block &operator[](std::size_t pos) const noexcept { return blocks[pos]; }
+};
@@ -0,0 +1,321 @@
+#include "json.hpp"
+
+#include <SFML/Graphics.hpp>
+#include <cctype>
+#include <cstddef>
+#include <cstdint>
+#include <cstdlib>
+#include <sstream>
+#include <stdexcept>
+#include <string>
+#include <vector>
+
+namespace json {
+  class Parser {
+    const std::string &text;
+    std::size_t at = 0;
+
+    [[noreturn]] void fail(const std::string &what) const {
+      throw std::runtime_error("invalid JSON at offset " + std::to_string(at) + ": " + what);
+    }
+
+    void skip_whitespace() {
+      while (at < text.size() && std::isspace(static_cast<unsigned char>(text[at])))
+        at++;
+    }
+
+    char peek() {
+      skip_whitespace();
+
+      if (at >= text.size())
+        fail("unexpected end of input");
+
+      return text[at];
+    }
+
+    void expect(char c) {
+      if (peek() != c)
+        fail(std::string("expected '") + c + "'");
+
+      at++;
+    }
+
+    bool consume(const std::string &word) {
+      if (text.compare(at, word.size(), word) != 0)
+        return false;
+
+      at += word.size();
+      return true;
+    }
+
+    std::string parse_string() {
+      expect('"');
+
+      std::string out;
+
+      while (at < text.size() && text[at] != '"') {
+        char c = text[at++];
+
+        if (c == '\\') {
+          if (at >= text.size())
+            fail("unterminated escape");
+
+          switch (char escaped = text[at++]) {
+          case 'n':
+            out += '\n';
+            break;
+          case 't':
+            out += '\t';
+            break;
+          case '"':
+          case '\\':
+          case '/':
+            out += escaped;
+            break;
+          default:
+            fail("unsupported escape");
+          }
+        } else {
+          out += c;
+        }
+      }
+
+      if (at >= text.size())
+        fail("unterminated string");
+
+      at++;
+      return out;
+    }
+
+    Value parse_value() {
+      Value value;
+
+      switch (peek()) {
+      case '{':
+        value.kind = Value::Kind::Object;
+        at++;
+
+        if (peek() == '}') {
+          at++;
+          break;
+        }
+
+        for (;;) {
+          value.keys.push_back(parse_string());
+          expect(':');
+          value.items.push_back(parse_value());
+
+          if (peek() != ',')
+            break;
+
+          at++;
+        }
+
+        expect('}');
+        break;
+      case '[':
+        value.kind = Value::Kind::Array;
+        at++;
+
+        if (peek() == ']') {
+          at++;
+          break;
+        }
+
+        for (;;) {
+          value.items.push_back(parse_value());
+
+          if (peek() != ',')
+            break;
+
+          at++;
+        }
+
+        expect(']');
+        break;
+      case '"':
+        value.kind = Value::Kind::String;
+        value.string = parse_string();
+        break;
+      default:
+        if (consume("true")) {
+          value.kind = Value::Kind::Bool;
+          value.boolean = true;
+        } else if (consume("false")) {
+          value.kind = Value::Kind::Bool;
+        } else if (consume("null")) {
+          value.kind = Value::Kind::Null;
+        } else {
+          const char *start = text.c_str() + at;
+          char *end = nullptr;
+
+          value.number = std::strtod(start, &end);
+          if (end == start)
+            fail("expected a value");
+
+          value.kind = Value::Kind::Number;
+          at += std::size_t(end - start);
+        }
+      }
+
+      return value;
+    }
+
+  public:
+    explicit Parser(const std::string &text) : text(text) {}
+
+    Value parse() {
+      auto value = parse_value();
+
+      skip_whitespace();
+      if (at != text.size())
+        fail("trailing characters");
+
+      return value;
+    }
+  };
+
+  Value parse(const std::string &text) { return Parser(text).parse(); }
+} // namespace json
+
+static char const *const block_type_names[] = {"vacant", "snake", "fruit", "wall", "painted"};
+static char const *const fruit_kind_names[] = {"normal", "reverse", "freeze", "bonus"};
+static char const *const direction_names[] = {"none",     "left",      "right",
+                                              "up",       "down",      "up-left",
+                                              "up-right", "down-left", "down-right"};
+
+// Looks `name` up in one of the name tables above and converts its index to `T`.
+template <typename T, std::size_t N>
+static T from_name(char const *const (&names)[N], const std::string &name) {
+  for (std::size_t i = 0; i < N; i++) {
+    if (name == names[i])
+      return T(i);
+  }
+
+  throw std::runtime_error("unknown name \"" + name + "\"");
+}
+
+std::string to_json(const Snake &snake, const Grid &grid, std::size_t score) {
+  std::ostringstream out;
+
+  const auto xy = [&](sf::Vector2u pos) {
+    out << '[' << pos.x << ", " << pos.y << ']';
+  };
+
+  out << "{\n  \"horizontal\": " << grid.horizontal() << ",\n  \"vertical\": " << grid.vertical()
+      << ",\n  \"score\": " << score << ",\n  \"blocks\": [";
+
+  for (std::size_t i = 0; i < grid.len(); i++) {
+    const auto &block = grid[i];
+    const auto colour = block.colour();
+
+    out << (i ? ",\n" : "\n") << "    {\"type\": \"" << block_type_names[std::size_t(block.type())]
+        << "\", \"fruit\": \"" << fruit_kind_names[std::size_t(block.fruit())]
+        << "\", \"colour\": [" << int(colour.r) << ", " << int(colour.g) << ", " << int(colour.b)
+        << ", " << int(colour.a) << "]}";
+  }
+
+  out << "\n  ],\n  \"snake\": {\n    \"head\": ";
+  xy(snake.head());
+  out << ",\n    \"body\": [";
+
+  for (std::size_t i = 0; i < snake.body().size(); i++) {
+    out << (i ? ", " : "");
+    xy(snake.body()[i]);
+  }
+
+  out << "],\n    \"direction\": \"" << direction_names[std::size_t(snake.direction())]
+      << "\"\n  }\n}\n";
+
+  return out.str();
+}
+
+std::size_t load_json(const std::string &text, Grid &grid, Snake &snake,
+                      sf::Vector2u resolution) {
+  const auto root = json::parse(text);
+
+  const auto horizontal = root["horizontal"].to_size();
+  const auto vertical = root["vertical"].to_size();
+  const auto score = root["score"].to_size();
+  const auto &blocks = root["blocks"].array();
+
+  if (horizontal == 0 || vertical == 0 || blocks.size() != horizontal * vertical)
+    throw std::runtime_error("the number of blocks doesn't match the dimensions");
+
+  const auto position = [&](const json::Value &value) {
+    const auto &xy = value.array();
+
+    if (xy.size() != 2 || xy[0].to_size() >= horizontal || xy[1].to_size() >= vertical)
+      throw std::runtime_error("snake position outside the grid");
+
+    return sf::Vector2u(xy[0].to_size(), xy[1].to_size());
+  };
+
+  Grid loaded(horizontal, vertical, grid.origin(), resolution);
+  loaded.set_show_vacant(grid.show_vacant());
+  loaded.set_checkerboard(grid.checkerboard());
+  loaded.set_round_fruit(grid.round_fruit());
+
+  for (std::size_t i = 0; i < blocks.size(); i++) {
+    const auto &colour = blocks[i]["colour"].array();
+
+    if (colour.size() != 4)
+      throw std::runtime_error("colours must have four components");
+
+    loaded.set_type(i, from_name<BlockType>(block_type_names, blocks[i]["type"].str()));
+    loaded.set_fruit(i, from_name<FruitKind>(fruit_kind_names, blocks[i]["fruit"].str()));
+    loaded.set_colour(i, sf::Color(std::uint8_t(colour[0].to_size()),
+                                   std::uint8_t(colour[1].to_size()),
+                                   std::uint8_t(colour[2].to_size()),
+                                   std::uint8_t(colour[3].to_size())));
+  }
+
+  const auto &state = root["snake"];
+  const auto head = position(state["head"]);
+
+  std::vector<sf::Vector2u> body;
+  for (const auto &segment : state["body"].array()) {
+    body.push_back(position(segment));
+  }
+
+  for (const auto pos : body) {
+    if (loaded[pos].type() != BlockType::OccupiedSnake)
+      throw std::runtime_error("snake segment on a block that isn't marked as snake");
+  }
+
+  if (loaded[head].type() != BlockType::OccupiedSnake)
+    throw std::runtime_error("snake head on a block that isn't marked as snake");
+
+  std::vector<bool> seen(loaded.len(), false);
+  seen[loaded.to_index(head)] = true;
+
+  auto front = head;
+  for (const auto pos : body) {
+    if (seen[loaded.to_index(pos)])
+      throw std::runtime_error("two snake segments on the same block");
+
+    seen[loaded.to_index(pos)] = true;
+
+    const auto dx = pos.x > front.x ? pos.x - front.x : front.x - pos.x;
+    const auto dy = pos.y > front.y ? pos.y - front.y : front.y - pos.y;
+
+    // Diagonal moves leave segments corner to corner.
+    const bool next_to = snake.diagonal() ? dx <= 1 && dy <= 1 : dx + dy == 1;
+
+    if (!next_to)
+      throw std::runtime_error("snake segment that isn't next to the one in front of it");
+
+    front = pos;
+  }
+
+  for (std::size_t i = 0; i < loaded.len(); i++) {
+    if (loaded[i].type() == BlockType::OccupiedSnake && !seen[i])
+      throw std::runtime_error("block marked as snake that isn't part of it");
+  }
+
+  grid = std::move(loaded);
+  snake.restore(head, std::move(body),
+                from_name<Direction>(direction_names, state["direction"].str()));
+
+  return score;
+}
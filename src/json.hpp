@@ -0,0 +1,78 @@
+#pragma once
+
+#include "grid.hpp"
+#include "snake.hpp"
+
+#include <SFML/System/Vector2.hpp>
+#include <cmath>
+#include <cstddef>
+#include <cstdint>
+#include <limits>
+#include <stdexcept>
+#include <string>
+#include <vector>
+
+// Just enough JSON to write out the game state and read it back in.
+namespace json {
+  struct Value {
+    enum class Kind : std::uint8_t { Null, Bool, Number, String, Array, Object };
+
+    Kind kind = Kind::Null;
+    bool boolean = false;
+    double number = 0.0;
+    std::string string;
+    std::vector<Value> items;
+    // Objects keep their members in `keys`/`items` pairs, in order of appearance.
+    std::vector<std::string> keys;
+
+    const Value &operator[](const std::string &key) const {
+      if (kind != Kind::Object)
+        throw std::runtime_error("expected an object");
+
+      for (std::size_t i = 0; i < keys.size(); i++) {
+        if (keys[i] == key)
+          return items[i];
+      }
+
+      throw std::runtime_error("missing field \"" + key + "\"");
+    }
+
+    const std::vector<Value> &array() const {
+      if (kind != Kind::Array)
+        throw std::runtime_error("expected an array");
+
+      return items;
+    }
+
+    const std::string &str() const {
+      if (kind != Kind::String)
+        throw std::runtime_error("expected a string");
+
+      return string;
+    }
+
+    // Anything that doesn't fit a `std::size_t` exactly, which casting would quietly mangle, is
+    // turned away. The limit is one past the largest there is.
+    std::size_t to_size() const {
+      static const double limit = std::ldexp(1.0, std::numeric_limits<std::size_t>::digits);
+
+      if (kind != Kind::Number || !(number >= 0.0 && number < limit) ||
+          std::floor(number) != number)
+        throw std::runtime_error("expected a whole non-negative number");
+
+      return std::size_t(number);
+    }
+  };
+
+  Value parse(const std::string &text);
+} // namespace json
+
+// Only the logical state is written out, along with the score; block geometry is rebuilt from the
+// dimensions on load.
+std::string to_json(const Snake &snake, const Grid &grid, std::size_t score);
+
+// Replaces `grid` and `snake` with the state described by `text`, as written by `to_json`, and
+// returns the score. Throws `std::runtime_error`, leaving both alone, unless the snake is all in
+// one piece with every block marked as snake part of it.
+std::size_t load_json(const std::string &text, Grid &grid, Snake &snake,
+                      sf::Vector2u resolution);
@@ -1,409 +1,967 @@
+#include "snek.hpp"
+
 #include <SFML/Graphics.hpp>
 #include <SFML/Graphics/RenderWindow.hpp>
-#include <SFML/System/Vector2.hpp>
 #include <SFML/Window/VideoMode.hpp>
-#include <chrono>
+#include <algorithm>
+#include <cmath>
 #include <cstddef>
 #include <cstdint>
-#include <exception>
-#include <random>
-#include <thread>
-#include <tuple>
+#include <cstdlib>
+#include <ctime>
+#include <fstream>
+#include <iostream>
+#include <optional>
+#include <sstream>
+#include <stdexcept>
+#include <string>
+#include <utility>
 #include <vector>
 
-static constexpr float block_len = 25.0f;
+#ifndef SNEK_VERSION
+#define SNEK_VERSION "unknown"
+#endif
+
+static constexpr char const *default_config_path = "snek.conf";
+
+// Frames that take longer than this are reported as hitches.
+static constexpr float long_frame_seconds = 0.1f;
 
-enum class BlockType : std::uint8_t {
-  Vacant,
-  OccupiedSnake,
-  OccupiedFruit,
-};
+// The game always moves on by `fixed_step` seconds at a time, as many times a frame as the time
+// that has passed calls for, so how long frames take never changes how it plays. After a hitch it
+// catches up on no more than `max_lag` seconds, rather than playing a burst of moves all at once;
+// anything past that is dropped, so the game runs that much behind the clock. Whatever is left
+// over, less than a step, goes towards how far along its move the snake is drawn.
+static constexpr float fixed_step = 1.0f / 120.0f;
+static constexpr float max_lag = 0.25f;
 
-inline constexpr bool is_occupied(BlockType type) {
-  return type == BlockType::OccupiedFruit || type == BlockType::OccupiedSnake;
+static bool parse_bool(const std::string &value) {
+  if (value == "true" || value == "yes" || value == "on" || value == "1")
+    return true;
+  if (value == "false" || value == "no" || value == "off" || value == "0")
+    return false;
+
+  throw std::invalid_argument("expected true or false, got \"" + value + "\"");
 }
 
-class Block : public sf::Drawable {
-  BlockType m_type = BlockType::Vacant;
-  sf::Color m_colour = sf::Color::Green;
+static std::size_t parse_size(const std::string &value) {
+  std::size_t end = 0;
+  unsigned long number = 0;
+
+  try {
+    number = std::stoul(value, &end);
+  } catch (std::logic_error const &) {
+    end = 0;
+  }
+
+  if (end == 0 || end != value.size() || value[0] == '-')
+    throw std::invalid_argument("expected a whole number, got \"" + value + "\"");
+
+  return number;
+}
 
-  sf::VertexArray m_arr;
+static float parse_seconds(const std::string &value) {
+  std::size_t end = 0;
+  float number = 0.0f;
 
-public:
-  Block() noexcept
-  : m_type(BlockType::Vacant), m_colour(sf::Color::Green), m_arr(sf::LinesStrip, 5) {
-    set_colour(m_colour);
+  try {
+    number = std::stof(value, &end);
+  } catch (std::logic_error const &) {
+    end = 0;
   }
 
-  sf::Vector2f position() const noexcept { return m_arr[0].position; }
-  BlockType type() const noexcept { return m_type; }
-  sf::Color colour() const noexcept { return m_colour; }
+  if (end == 0 || end != value.size() || !(number > 0.0f))
+    throw std::invalid_argument("expected a positive number of seconds, got \"" + value + "\"");
 
-  void set_position(sf::Vector2f pos) noexcept {
-    m_arr[0].position = pos;
-    m_arr[1].position = sf::Vector2f(pos.x + block_len, pos.y);
-    m_arr[2].position = pos + sf::Vector2f(block_len, block_len);
-    m_arr[3].position = sf::Vector2f(pos.x, pos.y + block_len);
-    m_arr[4].position = pos;
+  return number;
+}
+
+static float parse_factor(const std::string &value) {
+  std::size_t end = 0;
+  float number = 0.0f;
+
+  try {
+    number = std::stof(value, &end);
+  } catch (std::logic_error const &) {
+    end = 0;
   }
 
-  void set_colour(sf::Color colour) noexcept {
-    for (std::size_t i = 0; i < m_arr.getVertexCount(); i++) {
-      m_arr[i].color = colour;
+  if (end == 0 || end != value.size() || !(number > 0.0f && number <= 1.0f))
+    throw std::invalid_argument("expected a number above 0 and at most 1, got \"" + value + "\"");
+
+  return number;
+}
+
+static std::vector<sf::Vector2u> parse_cells(const std::string &value) {
+  std::vector<sf::Vector2u> cells;
+
+  try {
+    const auto parsed = json::parse(value);
+
+    for (const auto &pair : parsed.array()) {
+      const auto &xy = pair.array();
+
+      if (xy.size() != 2)
+        throw std::runtime_error("expected [x, y] pairs");
+
+      cells.emplace_back(unsigned(xy[0].to_size()), unsigned(xy[1].to_size()));
     }
+  } catch (std::runtime_error const &ex) {
+    throw std::invalid_argument(ex.what());
   }
 
-  void set_type(BlockType type) noexcept {
-    switch (type) {
-    case BlockType::Vacant:
-      m_arr.setPrimitiveType(sf::LinesStrip);
-      break;
-    case BlockType::OccupiedSnake:
-    case BlockType::OccupiedFruit:
-      m_arr.setPrimitiveType(sf::Quads);
-      break;
-    }
+  return cells;
+}
+
+// Named after the local time, e.g. `snek-20240131-235959.png`.
+static std::string end_image_path() {
+  const auto now = std::time(nullptr);
+
+  char stamp[32];
+  std::strftime(stamp, sizeof(stamp), "%Y%m%d-%H%M%S", std::localtime(&now));
+
+  return std::string("snek-") + stamp + ".png";
+}
+
+// `[x, y]` in pixels.
+static sf::Vector2f parse_origin(const std::string &value) {
+  try {
+    const auto parsed = json::parse(value);
+    const auto &xy = parsed.array();
 
-    m_type = type;
+    if (xy.size() != 2)
+      throw std::runtime_error("expected [x, y] or centre");
+
+    return sf::Vector2f(float(xy[0].to_size()), float(xy[1].to_size()));
+  } catch (std::runtime_error const &ex) {
+    throw std::invalid_argument(ex.what());
   }
+}
 
-  void draw(sf::RenderTarget &target, sf::RenderStates states) const override {
-    target.draw(m_arr, states);
+// Applies a single setting. The names are the same on the command line (`--name=value`, or just
+// `--name` for switches) and in the config file (`name = value`).
+static void apply_option(Config &config, const std::string &name, const std::string &value) {
+  if (name == "shrink") {
+    config.shrink_on_collision = parse_bool(value);
+  } else if (name == "chaos") {
+    config.chaos = parse_bool(value);
+  } else if (name == "bonus-chance") {
+    config.bonus_chance = parse_factor(value);
+  } else if (name == "bonus-lifetime") {
+    config.bonus_lifetime = parse_seconds(value);
+  } else if (name == "freeze") {
+    config.freeze = parse_bool(value);
+  } else if (name == "setup") {
+    config.setup = parse_bool(value);
+  } else if (name == "auto-start") {
+    config.auto_start = parse_bool(value);
+  } else if (name == "editor") {
+    config.editor = parse_bool(value);
+  } else if (name == "fair") {
+    config.fair_spawn = parse_bool(value);
+  } else if (name == "slow-motion") {
+    config.slow_motion = parse_bool(value);
+  } else if (name == "high-contrast") {
+    config.high_contrast = parse_bool(value);
+  } else if (name == "snake-colour") {
+    config.snake_colour = colour::from_hex(value);
+  } else if (name == "checkerboard") {
+    config.checkerboard = parse_bool(value);
+  } else if (name == "grid-lines") {
+    config.grid_lines = parse_bool(value);
+  } else if (name == "paint") {
+    config.paint = parse_bool(value);
+  } else if (name == "spawn") {
+    if (value == "random") {
+      config.spawn_mode = SpawnMode::Random;
+    } else if (value == "center") {
+      config.spawn_mode = SpawnMode::Center;
+    } else {
+      throw std::invalid_argument("expected random or center, got \"" + value + "\"");
+    }
+  } else if (name == "zen") {
+    config.zen = parse_bool(value);
+  } else if (name == "zen-size") {
+    config.zen = true;
+    config.zen_size = parse_size(value);
+  } else if (name == "zen-interval") {
+    config.zen = true;
+    config.zen_interval = parse_seconds(value);
+  } else if (name == "score-popups") {
+    config.score_popups = parse_bool(value);
+  } else if (name == "minimap") {
+    config.minimap = parse_bool(value);
+  } else if (name == "round-fruit") {
+    config.round_fruit = parse_bool(value);
+  } else if (name == "interpolate") {
+    config.interpolate = parse_bool(value);
+  } else if (name == "segment-numbers") {
+    config.segment_numbers = parse_bool(value);
+  } else if (name == "eat-flash") {
+    config.eat_flash = parse_bool(value);
+  } else if (name == "end-image") {
+    config.end_image = parse_bool(value);
+  } else if (name == "idle-pause") {
+    config.idle_pause = parse_seconds(value);
+  } else if (name == "coyote-time") {
+    config.coyote_time = parse_seconds(value);
+  } else if (name == "practice-undo") {
+    config.practice_undo = parse_size(value);
+  } else if (name == "diagonal") {
+    config.diagonal = parse_bool(value);
+  } else if (name == "random-start") {
+    config.random_start = parse_bool(value);
+  } else if (name == "confirm-quit") {
+    config.confirm_quit = parse_bool(value);
+  } else if (name == "tail-safe") {
+    config.tail_safe = parse_bool(value);
+  } else if (name == "distinct-fruit-colours") {
+    config.distinct_fruit_colours = parse_bool(value);
+  } else if (name == "single-fruit") {
+    config.single_fruit = parse_bool(value);
+  } else if (name == "fruit-lifetime") {
+    config.fruit_lifetime = parse_seconds(value);
+  } else if (name == "fruit-tint") {
+    config.fruit_tint = parse_bool(value);
+  } else if (name == "spawn-interval") {
+    config.spawn_interval = parse_seconds(value);
+  } else if (name == "input-buffer") {
+    config.input_buffer = parse_size(value);
+  } else if (name == "fruit-density") {
+    config.fruit_density = parse_factor(value);
+  } else if (name == "initial-fruits") {
+    config.initial_fruits = parse_size(value);
+  } else if (name == "max-length") {
+    config.max_length = parse_size(value);
+
+    if (*config.max_length == 0)
+      throw std::invalid_argument("the snake is always at least one block long");
+  } else if (name == "walls") {
+    config.walls = parse_cells(value);
+  } else if (name == "fruit-sequence") {
+    config.fruit_sequence = parse_cells(value);
+  } else if (name == "fruit-sequence-random") {
+    config.fruit_sequence_random = parse_bool(value);
+  } else if (name == "level-fruits") {
+    config.level_fruits = parse_size(value);
+
+    if (config.level_fruits == 0)
+      throw std::invalid_argument("a level needs at least one fruit");
+  } else if (name == "rainbow") {
+    config.rainbow = parse_bool(value);
+  } else if (name == "length-meter") {
+    config.length_meter = parse_bool(value);
+  } else if (name == "magnet") {
+    config.magnet = parse_bool(value);
+  } else if (name == "death-marker") {
+    config.death_marker = parse_bool(value);
+  } else if (name == "title-screen") {
+    config.title_screen = parse_bool(value);
+  } else if (name == "progress-bar") {
+    config.progress_bar = parse_bool(value);
+  } else if (name == "levels") {
+    config.levels = parse_bool(value);
+  } else if (name == "level-speedup") {
+    config.levels = true;
+    config.level_speedup = parse_factor(value);
+  } else if (name == "level-walls") {
+    config.levels = true;
+    config.level_walls = parse_size(value);
+  } else if (name == "level-extra-fruits") {
+    config.levels = true;
+    config.level_extra_fruits = parse_size(value);
+  } else if (name == "speed-curve") {
+    config.speed_curve = parse_bool(value);
+  } else if (name == "curve-start") {
+    config.speed_curve = true;
+    config.curve_start = parse_seconds(value);
+  } else if (name == "curve-min") {
+    config.speed_curve = true;
+    config.curve_min = parse_seconds(value);
+  } else if (name == "curve-rate") {
+    config.speed_curve = true;
+    config.curve_rate = parse_factor(value);
+  } else if (name == "length-speedup") {
+    config.length_speedup = parse_bool(value);
+  } else if (name == "speed-step") {
+    config.length_speedup = true;
+    config.speed_step = parse_seconds(value);
+  } else if (name == "width" || name == "height") {
+    const auto blocks = parse_size(value);
+
+    if (blocks < min_grid_len)
+      throw std::invalid_argument("the grid needs to be at least " + std::to_string(min_grid_len) +
+                                  " blocks across");
+
+    (name == "width" ? config.horizontal : config.vertical) = blocks;
+  } else if (name == "origin") {
+    config.centred = value == "centre" || value == "center";
+
+    if (!config.centred) {
+      config.origin = parse_origin(value);
+    }
+  } else if (name == "seed") {
+    config.seed = std::uint32_t(parse_size(value));
+  } else if (name == "scenario") {
+    config.scenario_path = value;
+  } else if (name == "font") {
+    config.font_path = value;
+  } else if (name == "records-dir") {
+    config.records_dir = value;
+  } else {
+    throw std::invalid_argument("unknown option \"" + name + "\"");
   }
-};
+}
+
+static std::string trim(const std::string &text) {
+  const auto first = text.find_first_not_of(" \t\r");
+  if (first == std::string::npos)
+    return "";
 
-class Grid : public sf::Drawable {
-  std::size_t _horizontal, _vertical;
-  std::vector<Block> blocks;
+  return text.substr(first, text.find_last_not_of(" \t\r") - first + 1);
+}
 
-public:
-  Grid(std::size_t horizontal, std::size_t vertical, sf::Vector2f pos, sf::Vector2u resolution)
-    : _horizontal(horizontal), _vertical(vertical), blocks(horizontal * vertical) {
+// Reads `name = value` lines; blank lines and lines starting with `#` are skipped. A missing file
+// isn't an error since the defaults are fine without one.
+static void load_config_file(Config &config, const std::string &path) {
+  std::ifstream file(path);
+  std::string line;
 
-    const std::size_t max_blocks_horizontal =
-      std::floor(float(resolution.x - pos.x) / block_len);
-    const auto max_blocks_vertical =
-      std::size_t(std::floor(float(resolution.y - pos.y) / block_len));
+  for (std::size_t number = 1; std::getline(file, line); number++) {
+    line = trim(line);
 
-    const float first_x = pos.x;
+    if (line.empty() || line[0] == '#')
+      continue;
 
-    for (std::size_t x = 0, y = 0; y < std::min(max_blocks_vertical, vertical); y++) {
-      for (; x < std::min(max_blocks_horizontal, horizontal); x++) {
-        auto& block = blocks[x + y * horizontal];
-        block.set_position(pos);
+    const auto equals = line.find('=');
 
-        pos.x += block_len;
-      }
+    try {
+      if (equals == std::string::npos)
+        throw std::invalid_argument("expected `name = value`");
 
-      x = 0;
-      pos.x = first_x;
-      pos.y += block_len;
+      apply_option(config, trim(line.substr(0, equals)), trim(line.substr(equals + 1)));
+    } catch (std::invalid_argument const &ex) {
+      std::cerr << path << ':' << number << ": " << ex.what() << '\n';
     }
   }
+}
+
+// The config file is read first, so options on the command line override it.
+static Config parse_args(int argc, char **argv) {
+  Config config;
+  std::string config_path = default_config_path;
+
+  std::vector<std::pair<std::string, std::string>> options;
 
-  void draw(sf::RenderTarget &target, sf::RenderStates states) const override {
-    for (const auto &block : blocks) {
-      target.draw(block, states);
+  for (int i = 1; i < argc; i++) {
+    const std::string arg = argv[i];
+
+    if (arg.compare(0, 2, "--") != 0) {
+      std::cerr << "unexpected argument: " << arg << '\n';
+      continue;
+    }
+
+    const auto equals = arg.find('=');
+    auto name = arg.substr(2, equals - 2);
+    auto value = equals == std::string::npos ? "true" : arg.substr(equals + 1);
+
+    if (name == "config") {
+      config_path = value;
+    } else if (name == "info" || name == "version") {
+      config.print_info = true;
+    } else {
+      options.emplace_back(std::move(name), std::move(value));
     }
   }
 
-  std::size_t horizontal() const noexcept { return _horizontal; }
-  std::size_t vertical() const noexcept { return _vertical; }
-  std::size_t len() const noexcept { return blocks.size(); }
+  load_config_file(config, config_path);
 
-  Block &operator[](sf::Vector2u pos) { return blocks[pos.x + pos.y * horizontal()]; }
-  const Block &operator[](sf::Vector2u pos) const { return blocks[pos.x + pos.y * horizontal()]; }
+  for (const auto &[name, value] : options) {
+    try {
+      apply_option(config, name, value);
+    } catch (std::invalid_argument const &ex) {
+      std::cerr << "--" << name << ": " << ex.what() << '\n';
+    }
+  }
 
-  Block &operator[](std::size_t pos) noexcept { return blocks[pos]; }
-  const Block &operator[](std::size_t pos) const noexcept { return blocks[pos]; }
-};
+  // The grid size isn't known for certain until every option has been applied.
+  const auto outside = std::remove_if(config.walls.begin(), config.walls.end(), [&](auto pos) {
+    if (pos.x < config.horizontal && pos.y < config.vertical)
+      return false;
 
-sf::Vector2u operator+(sf::Vector2u lhs, sf::Vector2i rhs) {
-  return sf::Vector2u(uint(int(lhs.x) + rhs.x), uint(int(lhs.y) + rhs.y));
+    std::cerr << "walls: " << to_string(sf::Vector2i(pos)) << " is outside the "
+              << config.horizontal << 'x' << config.vertical << " grid\n";
+    return true;
+  });
+  config.walls.erase(outside, config.walls.end());
+
+  return config;
 }
 
-enum class Direction : uint8_t {
-  None,
-  Left,
-  Right,
-  Up,
-  Down,
-};
+// The setup screen's keys: left/right or -/+ for the width and up/down for the height, as long as
+// the window still fits on the screen. Returns whether the size changed.
+static bool resize_grid(Config &config, sf::Keyboard::Key key) {
+  const auto desktop = sf::VideoMode::getDesktopMode();
+  const auto meter = config.length_meter ? length_meter_width : 0.0f;
 
-sf::Vector2i to_pos(Direction direction) {
-  int x = 0;
-  int y = 0;
+  // `window_size` leaves a block's worth of margin.
+  const auto max_horizontal = std::size_t((float(desktop.width) - meter) / block_len) - 1;
+  const auto max_vertical = std::size_t(float(desktop.height) / block_len) - 1;
 
-  switch (direction) {
-  case Direction::Left:
-    x = -1;
+  const auto before = std::make_pair(config.horizontal, config.vertical);
+
+  switch (key) {
+  case sf::Keyboard::Left:
+  case sf::Keyboard::Hyphen:
+  case sf::Keyboard::Subtract:
+    config.horizontal = std::max(config.horizontal - 1, min_grid_len);
+    break;
+  case sf::Keyboard::Right:
+  case sf::Keyboard::Equal:
+  case sf::Keyboard::Add:
+    config.horizontal = std::min(config.horizontal + 1, std::max(max_horizontal, min_grid_len));
+    break;
+  case sf::Keyboard::Down:
+    config.vertical = std::max(config.vertical - 1, min_grid_len);
+    break;
+  case sf::Keyboard::Up:
+    config.vertical = std::min(config.vertical + 1, std::max(max_vertical, min_grid_len));
+    break;
+  default:
     break;
+  }
+
+  return std::make_pair(config.horizontal, config.vertical) != before;
+}
+
+// An arrow pressed while one at right angles to it is still held down goes between the two.
+static Direction with_held_arrow(Direction direct) {
+  const auto held = [](sf::Keyboard::Key key) { return sf::Keyboard::isKeyPressed(key); };
+
+  switch (direct) {
+  case Direction::Left:
   case Direction::Right:
-    x = 1;
+    if (held(sf::Keyboard::Up))
+      return diagonal(Direction::Up, direct);
+    if (held(sf::Keyboard::Down))
+      return diagonal(Direction::Down, direct);
     break;
   case Direction::Up:
-    y = -1;
-    break;
   case Direction::Down:
-    y = 1;
+    if (held(sf::Keyboard::Left))
+      return diagonal(direct, Direction::Left);
+    if (held(sf::Keyboard::Right))
+      return diagonal(direct, Direction::Right);
     break;
   default:
     break;
   }
 
-  return sf::Vector2i(x, y);
+  return direct;
 }
 
-struct MotorException : public std::exception {
-  const char *what() const noexcept override { return "cannot turn the opposite direction"; }
-};
-
-struct CollisionException : public std::exception {
-  const char *what() const noexcept override { return "collided with the snake's own body"; }
-};
+// Shows all of a `native`-sized view in a window of `window` pixels, scaled as far as it goes
+// without stretching it, and centred between bars along whichever side is left over.
+static sf::View letterbox(sf::Vector2u native, sf::Vector2u window) {
+  const auto scale_x = float(window.x) / float(native.x);
+  const auto scale_y = float(window.y) / float(native.y);
 
-namespace randomiser {
-  static std::random_device source;
-  static std::mt19937 generator(source());
+  // The fraction of the window the view takes up in either direction.
+  auto width = 1.0f;
+  auto height = 1.0f;
 
-  std::size_t gen(std::size_t min, std::size_t max) {
-    auto dist = std::uniform_int_distribution<std::size_t>(min, max);
-    return dist(generator);
+  if (scale_x > scale_y) {
+    width = scale_y / scale_x;
+  } else {
+    height = scale_x / scale_y;
   }
-} // namespace randomiser
 
-class Snake {
-  Grid &grid;
+  sf::View view(sf::FloatRect(0.0f, 0.0f, float(native.x), float(native.y)));
+  view.setViewport(sf::FloatRect((1.0f - width) / 2.0f, (1.0f - height) / 2.0f, width, height));
 
-  sf::Vector2u head_position;
-  std::vector<sf::Vector2u> body_positions;
+  return view;
+}
 
-  Direction _direction;
+// As much of a `world`-sized window as fits on the screen. The rest scrolls into view as the snake
+// goes, with `follow`.
+static sf::Vector2u visible_size(sf::Vector2u world) {
+  const auto desktop = sf::VideoMode::getDesktopMode();
 
-  void assert(sf::Vector2u pos) const {
-    if (pos.x >= grid.horizontal())
-      throw std::out_of_range("cannot move outside the grid horizontally");
-    if (pos.y >= grid.vertical())
-      throw std::out_of_range("cannot move outside the grid vertically");
+  return sf::Vector2u(std::min(world.x, desktop.width), std::min(world.y, desktop.height));
+}
 
-    if (grid[pos].type() == BlockType::OccupiedSnake)
-      throw CollisionException();
-  }
+// Centres `view` on `target`, as far as it can without showing anything past the edges of
+// `world`.
+static void follow(sf::View &view, sf::Vector2f target, sf::Vector2u world) {
+  const auto axis = [](float at, float half, float length) {
+    return length <= 2.0f * half ? length / 2.0f : std::clamp(at, half, length - half);
+  };
 
-  void assert_direction(Direction direct) const {
-    if ((direct == Direction::Left && _direction == Direction::Right) ||
-        (direct == Direction::Right && _direction == Direction::Left)) {
-      throw MotorException();
-    }
+  const auto half = view.getSize() / 2.0f;
 
-    if ((direct == Direction::Up && _direction == Direction::Down) ||
-        (direct == Direction::Down && _direction == Direction::Up)) {
-      throw MotorException();
-    }
-  }
+  view.setCenter(axis(target.x, half.x, float(world.x)), axis(target.y, half.y, float(world.y)));
+}
 
-  void update_pos(sf::Vector2u &pos, sf::Vector2u new_pos) {
-    grid[pos].set_type(BlockType::Vacant);
+// Formatted like the other durations in `print_info`.
+static std::string seconds_or_none(std::optional<float> secs) {
+  if (!secs)
+    return "none";
 
-    pos = new_pos;
+  std::ostringstream out;
+  out << *secs << 's';
 
-    grid[pos].set_type(BlockType::OccupiedSnake);
-  }
+  return out.str();
+}
 
-public:
-  Snake(Grid &grid) : grid(grid), head_position(), body_positions(), _direction(Direction::None) {
-    auto horizontal = randomiser::gen(0, grid.horizontal());
-    auto vertical = randomiser::gen(0, grid.vertical());
-    auto initial = sf::Vector2u(horizontal, vertical);
+// Everything that affects how a game plays, for attaching to bug reports.
+static void print_info(const Config &config) {
+  const auto window = window_size(config);
+
+  std::cout << "snek " << SNEK_VERSION << '\n'
+            << "grid: " << config.horizontal << 'x' << config.vertical << " blocks of "
+            << block_len << "px\n"
+            << "window: " << window.x << 'x' << window.y << '\n'
+            << "origin: "
+            << (config.centred ? std::string("centre")
+                               : to_string(sf::Vector2i(config.origin)))
+            << '\n'
+            << "movement interval: " << movement_interval << "s\n"
+            << "spawn interval: " << config.spawn_interval << "s\n"
+            << "fruit lifetime: " << seconds_or_none(config.fruit_lifetime) << '\n'
+            << "bonus fruit: "
+            << (config.bonus_chance ? std::to_string(std::lround(*config.bonus_chance * 100.0f)) +
+                                        "% chance, " + seconds_or_none(config.bonus_lifetime)
+                                    : std::string("none"))
+            << '\n'
+            << "idle pause: " << seconds_or_none(config.idle_pause) << '\n'
+            << "coyote time: " << config.coyote_time << "s\n"
+            << "input buffer: " << config.input_buffer << '\n'
+            << "initial fruits: " << config.initial_fruits << '\n'
+            << "fruit density: "
+            << (config.fruit_density
+                  ? std::to_string(target_fruit(config.horizontal * config.vertical,
+                                                *config.fruit_density)) +
+                      " fruit"
+                  : std::string("none"))
+            << '\n'
+            << "fruits per level: " << config.level_fruits << '\n'
+            << "max length: "
+            << (config.max_length ? std::to_string(*config.max_length) : std::string("none"))
+            << '\n'
+            << "walls: " << config.walls.size() << '\n'
+            << "fruit sequence: " << config.fruit_sequence.size()
+            << (config.fruit_sequence_random ? " then random\n" : "\n")
+            << "seed: " << randomiser::seed() << '\n'
+            << "spawn: " << (config.spawn_mode == SpawnMode::Center ? "center" : "random") << '\n'
+            << "snake colour: "
+            << (config.snake_colour ? colour::to_hex(*config.snake_colour) : std::string("theme"))
+            << '\n'
+            << "font: " << config.font_path << '\n'
+            << "records: " << (config.records_dir.empty() ? "." : config.records_dir) << '\n'
+            << "modes:";
+
+  const std::pair<bool, char const *> modes[] = {
+    {config.shrink_on_collision, "shrink"}, {config.chaos, "chaos"},
+    {config.editor, "editor"},              {config.fair_spawn, "fair"},
+    {config.slow_motion, "slow-motion"},    {config.paint, "paint"},
+    {config.progress_bar, "progress-bar"},  {config.magnet, "magnet"},
+    {config.length_meter, "length-meter"},  {config.rainbow, "rainbow"},
+    {config.freeze, "freeze"},              {config.single_fruit, "single-fruit"},
+    {config.auto_start, "auto-start"},      {config.high_contrast, "high-contrast"},
+    {config.tail_safe, "tail-safe"},        {config.end_image, "end-image"},
+    {config.checkerboard, "checkerboard"},  {config.setup, "setup"},
+    {config.eat_flash, "eat-flash"},        {config.segment_numbers, "segment-numbers"},
+    {config.diagonal, "diagonal"},          {config.fruit_tint, "fruit-tint"},
+    {config.minimap, "minimap"},            {config.confirm_quit, "confirm-quit"},
+    {config.random_start, "random-start"},  {config.score_popups, "score-popups"},
+    {config.distinct_fruit_colours, "distinct-fruit-colours"},
+  };
+
+  bool any = false;
+  for (const auto &[enabled, name] : modes) {
+    if (enabled) {
+      std::cout << ' ' << name;
+      any = true;
+    }
+  }
 
-    grid[initial].set_type(BlockType::OccupiedSnake);
+  if (config.levels) {
+    std::cout << " levels (x" << config.level_speedup << " interval, +" << config.level_walls
+              << " walls, +" << config.level_extra_fruits << " fruits)";
+    any = true;
+  }
 
-    head_position = std::move(initial);
+  if (config.practice_undo > 0) {
+    std::cout << " practice (undo " << config.practice_undo << ")";
+    any = true;
   }
 
-  void move() {
-    auto pos = to_pos(_direction);
+  if (config.speed_curve) {
+    std::cout << " speed-curve (" << config.curve_start << "s to " << config.curve_min << "s, "
+              << config.curve_rate << " a fruit)";
+    any = true;
+  }
 
-    auto old_pos = head_position;
-    auto new_pos = head_position + pos;
+  if (config.length_speedup) {
+    std::cout << " length-speedup (" << config.speed_step << "s a block, down to "
+              << min_movement_interval << "s)";
+    any = true;
+  }
 
-    assert(new_pos);
+  if (config.zen) {
+    std::cout << " zen (size " << config.zen_size << ", every " << config.zen_interval << "s)";
+    any = true;
+  }
 
-    bool was_occupied_by_fruit = grid[new_pos].type() == BlockType::OccupiedFruit;
+  std::cout << (any ? "\n" : " none\n");
+}
 
-    update_pos(head_position, new_pos);
+int main(int argc, char **argv) {
+  auto config = parse_args(argc, argv);
 
-    for (auto &pos : body_positions) {
-      auto before = pos;
+  std::optional<Scenario> scenario;
+  if (!config.scenario_path.empty()) {
+    try {
+      std::ifstream file(config.scenario_path);
+      if (!file)
+        throw std::runtime_error("could not open it");
 
-      update_pos(pos, old_pos);
+      std::ostringstream text;
+      text << file.rdbuf();
 
-      old_pos = before;
+      scenario = parse_scenario(text.str());
+    } catch (std::runtime_error const &ex) {
+      std::cerr << config.scenario_path << ": " << ex.what() << '\n';
+      return EXIT_FAILURE;
     }
 
-    if (was_occupied_by_fruit) {
-      grid[new_pos].set_colour(sf::Color::Green);
-
-      add_body();
-    }
+    // The window has to fit the scenario rather than the configured grid, so there's no picking
+    // another size either.
+    config.horizontal = scenario->horizontal;
+    config.vertical = scenario->vertical;
+    config.setup = false;
   }
 
-  Direction direction() const noexcept { return _direction; }
+  if (config.seed) {
+    randomiser::seed(*config.seed);
+  }
 
-  void set_direction(Direction direct) {
-    assert_direction(direct);
+  // Copies of the presets, so the snake keeps its colour from the config in both.
+  auto classic = theme::classic;
+  auto high_contrast = theme::high_contrast;
 
-    _direction = direct;
+  if (config.snake_colour) {
+    classic.snake = high_contrast.snake = *config.snake_colour;
   }
 
-  void add_body() {
-    auto tail = body_positions.empty() ? head_position : body_positions.back();
-
-    switch (_direction) {
-    case Direction::Left:
-      tail.x += 1;
-      break;
-    case Direction::Right:
-      tail.x -= 1;
-      break;
-    case Direction::Up:
-      tail.y += 1;
-      break;
-    case Direction::Down:
-      tail.y -= 1;
-      break;
-    default:
-      break;
-    }
-
-    grid[tail].set_type(BlockType::OccupiedSnake);
+  theme::set(config.high_contrast ? high_contrast : classic);
 
-    body_positions.emplace_back(std::move(tail));
+  if (config.print_info) {
+    print_info(config);
+    return 0;
   }
-};
 
-static sf::Color fruit_colours[] = {
-  sf::Color::Red, sf::Color::Blue, sf::Color(0xFF, 0xA5, 0x00), // Orange
-};
+  // The game is laid out for all of `size`, and the window shows `shown` of it.
+  auto size = window_size(config);
+  auto shown = visible_size(size);
+  sf::RenderWindow window(sf::VideoMode(shown.x, shown.y), title);
 
-static sf::Color gen_fruit_colour() {
-  return fruit_colours[randomiser::gen(0, 3)];
-}
+  auto view = letterbox(shown, shown);
 
-static Block& get_block_randomly(Grid &grid) {
-  std::size_t pos = 0;
+  sf::Font font;
+  const bool has_font = font.loadFromFile(config.font_path);
 
-  do {
-    pos = randomiser::gen(0, grid.len() - 1);
-  } while (is_occupied(grid[pos].type()));
+  // Everything but the configured game itself is left out of the demo behind the title screen.
+  auto demo_config = config;
+  demo_config.editor = false;
+  demo_config.paint = false;
+  demo_config.setup = false;
+  demo_config.fruit_sequence.clear();
 
-  return grid[pos];
-}
+  std::optional<Game> game;
 
-static void spawn_fruit(Grid &grid) {
-  auto& block = get_block_randomly(grid);
+  // Every real game starts out from the scenario, if there is one.
+  const auto new_game = [&] {
+    game.emplace(config, size);
 
-  block.set_type(BlockType::OccupiedFruit);
-  block.set_colour(gen_fruit_colour());
-}
+    if (scenario) {
+      game->load_scenario(*scenario);
+    }
+  };
 
-static char const *title = "Snek";
+  if (config.title_screen && !config.editor) {
+    game.emplace(demo_config, size, true);
+  } else {
+    new_game();
+  }
 
-enum class GameStates : uint8_t {
-  Start,
-  InProgress,
-  End,
-};
+  // Only touch the window title when the game's changes.
+  std::string shown_title = title;
 
-int main() {
-  sf::RenderWindow window(sf::VideoMode(500, 400), "Snek");
+  sf::Clock clock;
 
-  Grid grid(19, 15, sf::Vector2f(12.0f, 8.0f), window.getSize());
-  Snake snake(grid);
+  // Whether "quit?" is up. Everything stands still until it's answered.
+  bool confirming = false;
 
-  auto state = GameStates::Start;
+  // Time that has passed but that the game hasn't been moved on by yet, less than a `fixed_step`
+  // once it has caught up.
+  float lag = 0.0f;
 
-  sf::Clock clock;
+  const auto request_quit = [&] {
+    if (config.confirm_quit) {
+      confirming = true;
+      return;
+    }
 
-  auto movement_seconds = 0.0f;
-  auto spawn_seconds = 0.0f;
+    game->quit();
+    window.close();
+  };
 
   while (window.isOpen()) {
     auto event = sf::Event();
     while (window.pollEvent(event)) {
       switch (event.type) {
       case sf::Event::Closed:
-        window.close();
+        request_quit();
+        break;
+      case sf::Event::Resized:
+        view = letterbox(shown, sf::Vector2u(event.size.width, event.size.height));
+        window.setView(view);
         break;
-      case sf::Event::KeyPressed:
-        try {
+      case sf::Event::MouseButtonPressed:
+        if (game->state() == GameStates::Editor && event.mouseButton.button == sf::Mouse::Left) {
+          game->editor().press(window.mapPixelToCoords({event.mouseButton.x, event.mouseButton.y}));
+        }
+
+        break;
+      case sf::Event::MouseMoved:
+        if (game->state() == GameStates::Editor) {
+          game->editor().drag(window.mapPixelToCoords({event.mouseMove.x, event.mouseMove.y}));
+        }
+
+        break;
+      case sf::Event::MouseButtonReleased:
+        if (game->state() == GameStates::Editor && event.mouseButton.button == sf::Mouse::Left) {
+          game->editor().release();
+        }
+
+        break;
+      case sf::Event::KeyPressed: {
+        if (confirming) {
+          if (event.key.code == sf::Keyboard::Y) {
+            game->quit();
+            window.close();
+          } else if (event.key.code == sf::Keyboard::N || event.key.code == sf::Keyboard::Escape) {
+            confirming = false;
+          }
+
+          break;
+        }
+
+        if (game->state() == GameStates::Title) {
+          new_game();
+          break;
+        }
+
+        // R or Enter plays again and Escape quits. Backspace takes the fatal move back in practice
+        // games.
+        if (game->can_restart()) {
+          if (event.key.code == sf::Keyboard::R || event.key.code == sf::Keyboard::Enter) {
+            new_game();
+          } else if (event.key.code == sf::Keyboard::Backspace) {
+            game->undo();
+          } else if (event.key.code == sf::Keyboard::Escape) {
+            request_quit();
+          }
+
+          break;
+        }
+
+        if (game->wake())
+          break;
+
+        if (game->state() == GameStates::Setup) {
+          if (event.key.code == sf::Keyboard::Enter) {
+            config.setup = false;
+            new_game();
+          } else if (resize_grid(config, event.key.code)) {
+            size = window_size(config);
+            shown = visible_size(size);
+            window.setSize(shown);
+            view = letterbox(shown, shown);
+            window.setView(view);
+
+            if (!game->resize(config, size)) {
+              new_game();
+            }
+          }
+
+          break;
+        }
+
+        if (game->state() == GameStates::Editor) {
           switch (event.key.code) {
-          case sf::Keyboard::Left:
-            snake.set_direction(Direction::Left);
+          case sf::Keyboard::S:
+            if (event.key.control) {
+              game->set_message(
+                std::string(game->editor().save(level_path) ? "saved " : "could not save ") +
+                level_path);
+            } else {
+              game->editor().place_snake();
+            }
+
             break;
-          case sf::Keyboard::Right:
-            snake.set_direction(Direction::Right);
+          case sf::Keyboard::F:
+            game->editor().place_fruit();
             break;
-          case sf::Keyboard::Up:
-            snake.set_direction(Direction::Up);
+          case sf::Keyboard::Z:
+            if (event.key.control) {
+              game->editor().undo();
+            }
+
             break;
-          case sf::Keyboard::Down:
-            snake.set_direction(Direction::Down);
+          case sf::Keyboard::Enter:
+            game->start();
             break;
           default:
             break;
           }
-        } catch (MotorException const &ex) {
-          window.setTitle(sf::String(title) + " : " + ex.what());
+
+          break;
         }
 
+        auto direct = Direction::None;
+
+        switch (event.key.code) {
+        case sf::Keyboard::G:
+          game->grid().set_show_vacant(!game->grid().show_vacant());
+          break;
+        case sf::Keyboard::B:
+          game->grid().set_checkerboard(!game->grid().checkerboard());
+          break;
+        case sf::Keyboard::H:
+          theme::set(&theme::current() == &high_contrast ? classic : high_contrast);
+          game->grid().apply_theme(theme::current());
+          break;
+        case sf::Keyboard::C:
+          game->grid().clear_paint();
+          break;
+        case sf::Keyboard::M:
+          // Stays the way it was left for the next game too.
+          config.minimap = !config.minimap;
+          game->set_minimap(config.minimap);
+          break;
+        case sf::Keyboard::F5:
+          game->export_state(state_path);
+          break;
+        case sf::Keyboard::F9:
+          game->import_state(state_path);
+          break;
+        case sf::Keyboard::Space:
+          game->toggle_pause();
+          break;
+        case sf::Keyboard::Backspace:
+          game->undo();
+          break;
+        case sf::Keyboard::Escape:
+          // Without the prompt, Escape only quits once the game is over.
+          if (config.confirm_quit) {
+            request_quit();
+          }
+
+          break;
+        case sf::Keyboard::Left:
+          direct = Direction::Left;
+          break;
+        case sf::Keyboard::Right:
+          direct = Direction::Right;
+          break;
+        case sf::Keyboard::Up:
+          direct = Direction::Up;
+          break;
+        case sf::Keyboard::Down:
+          direct = Direction::Down;
+          break;
+        case sf::Keyboard::Numpad7:
+          direct = Direction::UpLeft;
+          break;
+        case sf::Keyboard::Numpad9:
+          direct = Direction::UpRight;
+          break;
+        case sf::Keyboard::Numpad1:
+          direct = Direction::DownLeft;
+          break;
+        case sf::Keyboard::Numpad3:
+          direct = Direction::DownRight;
+          break;
+        default:
+          break;
+        }
+
+        if (config.diagonal) {
+          direct = with_held_arrow(direct);
+        }
+
+        game->input(direct);
         break;
+      }
       default:
         break;
       }
     }
 
-    switch (state) {
-    case GameStates::Start:
-      if (snake.direction() != Direction::None) {
-        state = GameStates::InProgress;
-      }
+    // Restarted every frame, so time spent outside of a game never counts towards the next move.
+    const float secs = clock.restart().asSeconds();
 
-      break;
-    case GameStates::InProgress: {
-      float secs = clock.restart().asSeconds();
+    if (secs > long_frame_seconds) {
+      std::cerr << "warning: long frame of " << secs * 1000.0f << "ms\n";
+    }
 
-      movement_seconds += secs;
-      spawn_seconds += secs;
+    const bool was_over = game->over();
 
-      if (spawn_seconds >= 5.0f) {
-        spawn_fruit(grid);
+    if (!confirming) {
+      // Whatever is over `max_lag` never gets played.
+      lag = std::min(lag + secs, max_lag);
 
-        spawn_seconds = 0.0f;
+      while (lag >= fixed_step) {
+        game->advance(fixed_step);
+        lag -= fixed_step;
       }
+    }
 
-      if (movement_seconds >= 0.25f) {
-        try {
-          snake.move();
-        } catch (std::out_of_range const &ex) {
-          window.setTitle(sf::String(title) + " : " + ex.what() + "- over!");
-          state = GameStates::End;
-        } catch (CollisionException const &ex) {
-          window.setTitle(sf::String(title) + " : " + ex.what() + "- over!");
-          state = GameStates::End;
-        }
+    if (config.end_image && !was_over && game->over() && !game->demo()) {
+      const auto path = end_image_path();
 
-        movement_seconds = 0.0f;
+      if (!game->save_image(path, has_font ? &font : nullptr)) {
+        std::cerr << "couldn't save " << path << '\n';
       }
+    }
+
+    // The demo starts over whenever the autopilot gets it wrong.
+    if (game->demo() && game->over()) {
+      game.emplace(demo_config, size, true);
+    }
+
+    if (const auto current = game->window_title(); current != shown_title) {
+      shown_title = current;
+      window.setTitle(shown_title);
+    }
+
+    // Only a board too big for the screen needs to scroll.
+    if (shown != size) {
+      const auto head = game->grid().origin() +
+                        (sf::Vector2f(game->snake().head()) + sf::Vector2f(0.5f, 0.5f)) * block_len;
 
-      break;
+      follow(view, head, size);
+      window.setView(view);
     }
-    case GameStates::End:
-      break;
+
+    game->render(window, has_font ? &font : nullptr, lag);
+
+    if (confirming) {
+      draw_overlay(window, has_font ? &font : nullptr, "Quit? Y/N");
     }
 
-    window.clear(sf::Color::White);
-    window.draw(grid);
     window.display();
   }
 
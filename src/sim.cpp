@@ -0,0 +1,342 @@
+// Plays games of snek with no window at all, by autopilot, from a script of turns or by turning at
+// random, and prints a line of JSON for each one as it ends:
+//
+//   snek-sim --games=100 --seed=1 --horizontal=20 --vertical=15
+//   snek-sim --script=..DDLL.U
+//   snek-sim --games=1000 --random-turns=true --random-size=true
+//
+// Game `i` is played from seed `seed + i`, which the line reports, so any of them can be played
+// again on its own. A game that throws gets an "error" instead of a score, and one that catches
+// itself in a broken state ends with an "error"; either makes the exit status 1, which is what
+// the random runs are there to catch.
+#include "snek.hpp"
+
+#include <algorithm>
+#include <cstddef>
+#include <cstdint>
+#include <exception>
+#include <iterator>
+#include <iostream>
+#include <memory>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <utility>
+#include <vector>
+
+// Where a game is cut off if it hasn't ended by then, in movement intervals.
+static constexpr std::size_t default_max_ticks = 100000;
+
+struct Options {
+  std::size_t games = 1;
+  std::optional<std::uint32_t> seed;
+  std::size_t horizontal = 20;
+  std::size_t vertical = 20;
+  std::size_t max_ticks = default_max_ticks;
+  // One character per move: L, R, U or D to turn before it, or `.` to carry on. The snake goes
+  // straight on once it runs out. The autopilot steers when there isn't one.
+  std::optional<std::string> script;
+  // Instead of a script, a quarter turn either way or no turn at all, picked at random every tick.
+  bool random_turns = false;
+  // Make each grid anything from `min_grid_len` up to `horizontal` by `vertical` blocks.
+  bool random_size = false;
+
+  // What the games have to add up to, or the exit status is 1: at least this many that ended with
+  // the head in the body, at least one snake this long, and at least this many filled grids. What
+  // a run can't fail on isn't testing much.
+  std::size_t min_collisions = 0;
+  std::size_t min_length = 0;
+  std::size_t min_wins = 0;
+};
+
+static std::size_t parse_size(const std::string &value) {
+  std::size_t end = 0;
+  unsigned long number = 0;
+
+  try {
+    number = std::stoul(value, &end);
+  } catch (std::logic_error const &) {
+    end = 0;
+  }
+
+  if (end == 0 || end != value.size() || value[0] == '-')
+    throw std::invalid_argument("expected a whole number, got \"" + value + "\"");
+
+  return number;
+}
+
+static bool parse_bool(const std::string &value) {
+  if (value == "true")
+    return true;
+  if (value == "false")
+    return false;
+
+  throw std::invalid_argument("expected true or false, got \"" + value + "\"");
+}
+
+static Direction scripted_turn(char c) {
+  switch (c) {
+  case 'L':
+    return Direction::Left;
+  case 'R':
+    return Direction::Right;
+  case 'U':
+    return Direction::Up;
+  case 'D':
+    return Direction::Down;
+  case '.':
+    return Direction::None;
+  default:
+    throw std::invalid_argument(std::string("unknown turn '") + c + "' in the script");
+  }
+}
+
+static void apply_option(Options &options, const std::string &name, const std::string &value) {
+  if (name == "games") {
+    options.games = parse_size(value);
+  } else if (name == "seed") {
+    options.seed = std::uint32_t(parse_size(value));
+  } else if (name == "horizontal" || name == "vertical") {
+    const auto blocks = parse_size(value);
+
+    if (blocks < min_grid_len)
+      throw std::invalid_argument("the grid needs to be at least " + std::to_string(min_grid_len) +
+                                  " blocks across");
+
+    (name == "horizontal" ? options.horizontal : options.vertical) = blocks;
+  } else if (name == "max-ticks") {
+    options.max_ticks = parse_size(value);
+  } else if (name == "script") {
+    for (const auto c : value) {
+      scripted_turn(c);
+    }
+
+    options.script = value;
+  } else if (name == "random-turns") {
+    options.random_turns = parse_bool(value);
+  } else if (name == "random-size") {
+    options.random_size = parse_bool(value);
+  } else if (name == "min-collisions") {
+    options.min_collisions = parse_size(value);
+  } else if (name == "min-length") {
+    options.min_length = parse_size(value);
+  } else if (name == "min-wins") {
+    options.min_wins = parse_size(value);
+  } else {
+    throw std::invalid_argument("unknown option");
+  }
+}
+
+// Games that got cut off at the tick limit have none, or were quit.
+static char const *ending_name(std::optional<Ending> ending) {
+  if (!ending)
+    return "tick-limit";
+
+  switch (*ending) {
+  case Ending::Collision:
+    return "collision";
+  case Ending::OutOfBounds:
+    return "out-of-bounds";
+  case Ending::Wall:
+    return "wall";
+  case Ending::Won:
+    return "won";
+  case Ending::Error:
+    return "error";
+  case Ending::Quit:
+    break;
+  }
+
+  return "tick-limit";
+}
+
+// A quarter turn from `heading`, clockwise or not.
+static Direction quarter_turn(Direction heading, bool clockwise) {
+  switch (heading) {
+  case Direction::Up:
+    return clockwise ? Direction::Right : Direction::Left;
+  case Direction::Right:
+    return clockwise ? Direction::Down : Direction::Up;
+  case Direction::Down:
+    return clockwise ? Direction::Left : Direction::Right;
+  case Direction::Left:
+    return clockwise ? Direction::Up : Direction::Down;
+  default:
+    return Direction::None;
+  }
+}
+
+// Never straight back, which the game would only refuse, so every pick is a turn that happens.
+// Nor off the edge of the grid unless there's no other way, since that ends a game before the snake
+// has grown enough to get in its own way.
+static Direction random_turn(const Snake &snake, const Grid &grid) {
+  const auto heading = snake.heading();
+  const auto head = grid.to_index(snake.head());
+
+  std::vector<Direction> turns;
+
+  for (const auto turn :
+       {Direction::None, quarter_turn(heading, true), quarter_turn(heading, false)}) {
+    const auto way = turn == Direction::None ? snake.direction() : turn;
+
+    if (way != Direction::None && grid.neighbour(head, way, false)) {
+      turns.push_back(turn);
+    }
+  }
+
+  if (turns.empty())
+    return Direction::None;
+
+  return turns[randomiser::gen(0, turns.size() - 1)];
+}
+
+// For error messages, which may well quote something.
+static std::string escape(const std::string &text) {
+  std::string out;
+
+  for (const auto c : text) {
+    if (c == '"' || c == '\\') {
+      out += '\\';
+    }
+
+    out += c;
+  }
+
+  return out;
+}
+
+// How a game went, for what the whole run has to add up to.
+struct Outcome {
+  // None for a game cut off at the tick limit.
+  std::optional<Ending> ending;
+  std::size_t length;
+};
+
+// Plays one game to the end, or to the tick limit, and prints how it went.
+static Outcome play(const Options &options, std::uint32_t seed) {
+  randomiser::seed(seed);
+
+  Config config;
+  config.horizontal =
+    options.random_size ? randomiser::gen(min_grid_len, options.horizontal) : options.horizontal;
+  config.vertical =
+    options.random_size ? randomiser::gen(min_grid_len, options.vertical) : options.vertical;
+  config.keep_records = false;
+
+  const bool steered = options.script || options.random_turns;
+  // The first move needs a direction to go in before anything is turned.
+  config.auto_start = steered;
+
+  // The window the game would have, though nothing is ever drawn.
+  Game game(config, window_size(config), !steered);
+
+  if (options.script) {
+    std::vector<Direction> turns;
+
+    for (const auto c : *options.script) {
+      turns.push_back(scripted_turn(c));
+    }
+
+    game.set_controller(std::make_unique<ReplayController>(std::move(turns)));
+  }
+
+  std::size_t moves = 0;
+  std::optional<Ending> ending;
+
+  game.set_observer([&](const GameEvent &event) {
+    if (event.kind == GameEvent::Kind::Moved) {
+      moves++;
+    } else if (event.kind == GameEvent::Kind::Ended) {
+      ending = event.ending;
+    }
+  });
+
+  for (std::size_t tick = 0; tick < options.max_ticks && !game.over(); tick++) {
+    if (options.random_turns) {
+      game.input(random_turn(game.snake(), game.grid()));
+    }
+
+    // Never less than the movement interval, so there's a move on every tick.
+    game.advance(movement_interval);
+  }
+
+  if (!game.over()) {
+    game.quit();
+  }
+
+  std::cout << "{\"seed\": " << seed << ", \"horizontal\": " << config.horizontal
+            << ", \"vertical\": " << config.vertical << ", \"score\": " << game.score()
+            << ", \"length\": " << game.snake().len() << ", \"moves\": " << moves
+            << ", \"ending\": \"" << ending_name(ending) << "\"}\n";
+
+  return Outcome{ending, game.snake().len()};
+}
+
+int main(int argc, char **argv) {
+  Options options;
+
+  for (int i = 1; i < argc; i++) {
+    const std::string arg = argv[i];
+    const auto equals = arg.find('=');
+
+    if (arg.compare(0, 2, "--") != 0 || equals == std::string::npos) {
+      std::cerr << "expected --name=value, got " << arg << '\n';
+      return 1;
+    }
+
+    const auto name = arg.substr(2, equals - 2);
+
+    try {
+      apply_option(options, name, arg.substr(equals + 1));
+    } catch (std::invalid_argument const &ex) {
+      std::cerr << "--" << name << ": " << ex.what() << '\n';
+      return 1;
+    }
+  }
+
+  if (options.script && options.random_turns) {
+    std::cerr << "--script and --random-turns can't both steer\n";
+    return 1;
+  }
+
+  const auto first = options.seed.value_or(randomiser::seed());
+  bool failed = false;
+
+  std::size_t collisions = 0;
+  std::size_t longest = 0;
+  std::size_t wins = 0;
+
+  for (std::size_t i = 0; i < options.games; i++) {
+    const auto seed = std::uint32_t(first + i);
+
+    try {
+      const auto outcome = play(options, seed);
+
+      failed = failed || outcome.ending == Ending::Error;
+      collisions += outcome.ending == Ending::Collision;
+      longest = std::max(longest, outcome.length);
+      wins += outcome.ending == Ending::Won;
+    } catch (std::exception const &ex) {
+      std::cout << "{\"seed\": " << seed << ", \"error\": \"" << escape(ex.what()) << "\"}\n";
+      failed = true;
+    }
+  }
+
+  if (collisions < options.min_collisions) {
+    std::cerr << "only " << collisions << " games ended in a collision, expected at least "
+              << options.min_collisions << '\n';
+    failed = true;
+  }
+  if (longest < options.min_length) {
+    std::cerr << "the longest snake was " << longest << " blocks, expected at least "
+              << options.min_length << '\n';
+    failed = true;
+  }
+  if (wins < options.min_wins) {
+    std::cerr << "only " << wins << " games filled the grid, expected at least " << options.min_wins
+              << '\n';
+    failed = true;
+  }
+
+  return failed ? 1 : 0;
+}
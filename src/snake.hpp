@@ -0,0 +1,424 @@
+#pragma once
+
+#include "config.hpp"
+#include "grid.hpp"
+#include "theme.hpp"
+#include "utils.hpp"
+
+#include <SFML/Graphics.hpp>
+#include <SFML/System/Vector2.hpp>
+#include <algorithm>
+#include <cstddef>
+#include <exception>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <vector>
+
+struct MotorException : public std::exception {
+  const char *what() const noexcept override { return "cannot turn the opposite direction"; }
+};
+
+inline std::string to_string(sf::Vector2i pos) {
+  return "(" + std::to_string(pos.x) + ", " + std::to_string(pos.y) + ")";
+}
+
+// The head would have left the grid for `pos`.
+struct OutOfBoundsException : public std::out_of_range {
+  sf::Vector2i pos;
+
+  OutOfBoundsException(const char *direction, sf::Vector2i pos)
+    : std::out_of_range(std::string("cannot move outside the grid ") + direction + ", to " +
+                        to_string(pos)),
+      pos(pos) {}
+};
+
+// The head ran into the body at `pos`.
+struct CollisionException : public std::exception {
+  sf::Vector2u pos;
+  std::string message;
+
+  explicit CollisionException(sf::Vector2u pos)
+    : pos(pos), message("collided with the snake's own body at " + to_string(sf::Vector2i(pos))) {}
+
+  const char *what() const noexcept override { return message.c_str(); }
+};
+
+// The head ran into a wall at `pos`.
+struct WallException : public std::exception {
+  sf::Vector2u pos;
+  std::string message;
+
+  explicit WallException(sf::Vector2u pos)
+    : pos(pos), message("crashed into a wall at " + to_string(sf::Vector2i(pos))) {}
+
+  const char *what() const noexcept override { return message.c_str(); }
+};
+
+class Snake {
+  Grid &grid;
+  const Config &config;
+
+  sf::Vector2u head_position;
+  // Ordered from the head to the tail: `body_positions[0]` is the segment right behind the head
+  // and the last one is the tip of the tail.
+  std::vector<sf::Vector2u> body_positions;
+
+  Direction _direction;
+  bool _near_miss = false;
+
+  void assert(sf::Vector2i target) const {
+    if (target.x < 0 || std::size_t(target.x) >= grid.horizontal())
+      throw OutOfBoundsException("horizontally", target);
+    if (target.y < 0 || std::size_t(target.y) >= grid.vertical())
+      throw OutOfBoundsException("vertically", target);
+
+    const auto pos = sf::Vector2u(target);
+
+    if (grid[pos].type() == BlockType::OccupiedSnake)
+      throw CollisionException(pos);
+    if (grid[pos].type() == BlockType::Wall)
+      throw WallException(pos);
+  }
+
+  // Checked against the way the snake last moved rather than `_direction`, which may have been
+  // turned since, so no number of turns between two moves can take the head back into the neck.
+  void assert_direction(Direction direct) const {
+    const auto moving = heading();
+
+    if (moving != Direction::None && direct == opposite(moving))
+      throw MotorException();
+  }
+
+  bool can_enter(sf::Vector2u pos) const {
+    if (pos.x >= grid.horizontal() || pos.y >= grid.vertical())
+      return false;
+
+    return grid[pos].type() != BlockType::OccupiedSnake && grid[pos].type() != BlockType::Wall;
+  }
+
+  // Picks a vacant cell to start on, preferring ones the snake can move away from.
+  sf::Vector2u spawn_position() const {
+    static constexpr std::size_t random_attempts = 64;
+
+    if (config.spawn_mode == SpawnMode::Center) {
+      const auto center =
+        grid.to_xy(grid.horizontal() / 2 + grid.vertical() / 2 * grid.horizontal());
+
+      // An editor level might have put something there or walled it in; fall back to a random
+      // cell then.
+      if (!is_occupied(grid[center].type()) && has_room(center))
+        return center;
+    }
+
+    for (std::size_t attempt = 0; attempt < random_attempts; attempt++) {
+      auto pos = grid.to_xy(randomiser::gen(0, grid.len() - 1));
+
+      if (!is_occupied(grid[pos].type()) && has_room(pos))
+        return pos;
+    }
+
+    std::optional<sf::Vector2u> fallback;
+
+    for (std::size_t i = 0; i < grid.len(); i++) {
+      auto pos = grid.to_xy(i);
+
+      if (is_occupied(grid[pos].type()))
+        continue;
+      if (has_room(pos))
+        return pos;
+      if (!fallback)
+        fallback = pos;
+    }
+
+    if (!fallback)
+      throw std::runtime_error("no vacant cell to place the snake on");
+
+    return *fallback;
+  }
+
+  // Removes the segment at `index` and every segment behind it.
+  void cut_at(std::size_t index) {
+    for (auto i = index; i < body_positions.size(); i++) {
+      grid.set_type(body_positions[i], BlockType::Vacant);
+      grid.set_colour(body_positions[i], theme::current().lines);
+    }
+
+    body_positions.erase(body_positions.begin() + index, body_positions.end());
+  }
+
+  // Grows the tail back into `cell`, which it only just moved out of.
+  void add_body(sf::Vector2u cell) {
+    grid.set_type(cell, BlockType::OccupiedSnake);
+    grid.set_colour(cell, theme::current().snake);
+
+    body_positions.push_back(cell);
+  }
+
+  void update_pos(sf::Vector2u &pos, sf::Vector2u new_pos) {
+    if (config.paint) {
+      grid.set_type(pos, BlockType::Painted);
+      grid.set_colour(pos, paint_colour);
+    } else {
+      grid.set_type(pos, BlockType::Vacant);
+      grid.set_colour(pos, theme::current().lines);
+    }
+
+    pos = new_pos;
+
+    grid.set_type(pos, BlockType::OccupiedSnake);
+    grid.set_colour(pos, theme::current().snake);
+  }
+
+public:
+  Snake(Grid &grid, const Config &config)
+    : grid(grid), config(config), head_position(), body_positions(), _direction(Direction::None) {
+    auto initial = spawn_position();
+
+    grid.set_type(initial, BlockType::OccupiedSnake);
+    grid.set_colour(initial, theme::current().snake);
+
+    head_position = std::move(initial);
+  }
+
+  // Returns the kind of fruit that was eaten, if any.
+  std::optional<FruitKind> move() {
+    auto pos = to_pos(_direction);
+
+    auto old_pos = head_position;
+    auto new_pos = head_position + pos;
+
+    // The tail moves out of the way on the same tick. It only stays put when the snake grows, and
+    // here that happens on the move that eats, which the tail's block can't be the target of.
+    const bool into_tail =
+      config.tail_safe && !body_positions.empty() && new_pos == body_positions.back();
+
+    if (!into_tail && config.shrink_on_collision && new_pos.x < grid.horizontal() &&
+        new_pos.y < grid.vertical() && grid[new_pos].type() == BlockType::OccupiedSnake) {
+      auto hit = std::find(body_positions.begin(), body_positions.end(), new_pos);
+      if (hit != body_positions.end()) {
+        cut_at(std::size_t(hit - body_positions.begin()));
+      }
+    }
+
+    if (!into_tail) {
+      assert(next_head());
+    }
+
+    bool was_occupied_by_fruit = grid[new_pos].type() == BlockType::OccupiedFruit;
+    auto fruit = grid[new_pos].fruit();
+
+    update_pos(head_position, new_pos);
+
+    // Each segment steps into the cell the one in front of it just left, so afterwards `old_pos` is
+    // where the tail used to be.
+    for (auto &pos : body_positions) {
+      auto before = pos;
+
+      update_pos(pos, old_pos);
+
+      old_pos = before;
+    }
+
+    // Moving the tail on just now cleared the block the head had moved into.
+    if (into_tail) {
+      grid.set_type(head_position, BlockType::OccupiedSnake);
+      grid.set_colour(head_position, theme::current().snake);
+    }
+
+    _near_miss = std::any_of(std::begin(all_directions), std::end(all_directions), [&](auto d) {
+      const auto next = head_position + to_pos(d);
+
+      if (next.x >= grid.horizontal() || next.y >= grid.vertical())
+        return false;
+      // The segment right behind the head is always next to it.
+      if (!body_positions.empty() && next == body_positions.front())
+        return false;
+
+      return grid[next].type() == BlockType::OccupiedSnake || grid[next].type() == BlockType::Wall;
+    });
+
+    if (was_occupied_by_fruit) {
+      grid.set_colour(new_pos, theme::current().snake);
+
+      // Past the cap, fruit still counts but the snake stays the same length. It can't outgrow the
+      // grid either, should anything ever let it get that far.
+      if ((!config.max_length || len() < *config.max_length) && len() < grid.len()) {
+        add_body(old_pos);
+      }
+    }
+
+#ifndef NDEBUG
+    validate();
+#else
+    check_overlap();
+#endif
+
+    if (was_occupied_by_fruit)
+      return fruit;
+
+    return std::nullopt;
+  }
+
+  // The part of `validate` cheap enough for release builds to do after every move: throws
+  // `std::logic_error` if the snake is longer than the grid has blocks, or any segment is off the
+  // grid or on the same block as another.
+  void check_overlap() const {
+    if (len() > grid.len())
+      throw std::logic_error("the snake is longer than the grid has blocks");
+
+    // Sorted rather than marked off on a grid's worth of flags, so it's only as slow as the snake
+    // is long.
+    std::vector<std::size_t> taken;
+    taken.reserve(len());
+
+    for (const auto pos : cells()) {
+      if (pos.x >= grid.horizontal() || pos.y >= grid.vertical())
+        throw std::logic_error("snake segment " + std::to_string(taken.size()) +
+                               " is off the grid");
+
+      taken.push_back(grid.to_index(pos));
+    }
+
+    std::sort(taken.begin(), taken.end());
+
+    if (std::adjacent_find(taken.begin(), taken.end()) != taken.end())
+      throw std::logic_error("two snake segments are on the same block");
+  }
+
+  // Throws `std::logic_error` unless every segment is on the grid, marked as snake and right next
+  // to the one in front of it, and no two of them share a block.
+  void validate() const {
+    check_overlap();
+
+    const auto all = cells();
+
+    for (std::size_t i = 0; i < all.size(); i++) {
+      if (grid[all[i]].type() != BlockType::OccupiedSnake)
+        throw std::logic_error("snake segment " + std::to_string(i) + " isn't marked on the grid");
+
+      if (i == 0)
+        continue;
+
+      const auto dx = all[i].x > all[i - 1].x ? all[i].x - all[i - 1].x : all[i - 1].x - all[i].x;
+      const auto dy = all[i].y > all[i - 1].y ? all[i].y - all[i - 1].y : all[i - 1].y - all[i].y;
+
+      // Diagonal moves leave segments corner to corner.
+      const bool next_to = config.diagonal ? dx <= 1 && dy <= 1 && dx + dy > 0 : dx + dy == 1;
+
+      if (!next_to)
+        throw std::logic_error("snake segment " + std::to_string(i) +
+                               " isn't next to the one in front of it");
+    }
+  }
+
+  sf::Vector2u head() const noexcept { return head_position; }
+
+  // Where the head is about to go, which may be off the grid.
+  sf::Vector2i next_head() const noexcept {
+    return sf::Vector2i(int(head_position.x), int(head_position.y)) + to_pos(_direction);
+  }
+
+  // Whether the last move put the head right next to a wall or the rest of the body.
+  bool near_miss() const noexcept { return _near_miss; }
+
+  // Everything but the head, nearest to it first.
+  const std::vector<sf::Vector2u> &body() const noexcept { return body_positions; }
+
+  // Every cell of the snake, from the head to the tip of the tail.
+  std::vector<sf::Vector2u> cells() const {
+    std::vector<sf::Vector2u> all;
+    all.reserve(len());
+
+    all.push_back(head_position);
+    all.insert(all.end(), body_positions.begin(), body_positions.end());
+
+    return all;
+  }
+
+  // The smallest rectangle of blocks that holds every segment, head included.
+  sf::Rect<unsigned> bounding_box() const {
+    auto low = head_position;
+    auto high = head_position;
+
+    for (const auto pos : body_positions) {
+      low = sf::Vector2u(std::min(low.x, pos.x), std::min(low.y, pos.y));
+      high = sf::Vector2u(std::max(high.x, pos.x), std::max(high.y, pos.y));
+    }
+
+    return sf::Rect<unsigned>(low, high - low + sf::Vector2u(1, 1));
+  }
+
+  // Takes over a body that is already laid out on the grid, e.g. when loading a saved game.
+  void restore(sf::Vector2u head, std::vector<sf::Vector2u> body, Direction direct) {
+    head_position = head;
+    body_positions = std::move(body);
+    _direction = direct;
+  }
+
+  // Whether there is at least one direction from `pos` that doesn't kill the snake.
+  bool has_room(sf::Vector2u pos) const {
+    return std::any_of(std::begin(all_directions), std::end(all_directions),
+                       [&](Direction direct) { return can_enter(pos + to_pos(direct)); });
+  }
+  Direction direction() const noexcept { return _direction; }
+
+  // The way the last move went, from the segment behind the head. Until there is one, that's just
+  // the way the snake is facing.
+  Direction heading() const noexcept {
+    if (body_positions.empty())
+      return _direction;
+
+    const auto neck = body_positions.front();
+
+    return direction_of(sf::Vector2i(int(head_position.x) - int(neck.x),
+                                     int(head_position.y) - int(neck.y)));
+  }
+
+  // Whether the head may move into the block the tail is just leaving.
+  bool tail_safe() const noexcept { return config.tail_safe; }
+
+  // Whether segments may be corner to corner, after diagonal moves.
+  bool diagonal() const noexcept { return config.diagonal; }
+
+  // Number of cells the snake occupies, head included.
+  std::size_t len() const noexcept { return body_positions.size() + 1; }
+
+  void set_direction(Direction direct) {
+    assert_direction(direct);
+
+    _direction = direct;
+  }
+
+  // Moves a snake that hasn't grown yet to another vacant cell.
+  void relocate(sf::Vector2u pos) {
+    if (!body_positions.empty() || is_occupied(grid[pos].type()))
+      return;
+
+    update_pos(head_position, pos);
+  }
+
+  // Starts over as a new snake on a grid that may have been laid out again since, so only the
+  // segments still on it are cleared.
+  void respawn() {
+    body_positions.insert(body_positions.begin(), head_position);
+
+    for (const auto pos : body_positions) {
+      if (pos.x >= grid.horizontal() || pos.y >= grid.vertical())
+        continue;
+      if (grid[pos].type() != BlockType::OccupiedSnake)
+        continue;
+
+      grid.set_type(pos, BlockType::Vacant);
+      grid.set_colour(pos, theme::current().lines);
+    }
+
+    body_positions.clear();
+    _direction = Direction::None;
+    _near_miss = false;
+
+    head_position = spawn_position();
+    grid.set_type(head_position, BlockType::OccupiedSnake);
+    grid.set_colour(head_position, theme::current().snake);
+  }
+};
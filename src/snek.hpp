@@ -0,0 +1,13 @@
+#pragma once
+
+// Everything it takes to run a game of snek from your own loop.
+#include "colour.hpp"
+#include "config.hpp"
+#include "controller.hpp"
+#include "game.hpp"
+#include "grid.hpp"
+#include "json.hpp"
+#include "snake.hpp"
+#include "stats.hpp"
+#include "theme.hpp"
+#include "utils.hpp"
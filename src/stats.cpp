@@ -0,0 +1,73 @@
+#include "stats.hpp"
+#include "json.hpp"
+
+#include <algorithm>
+#include <cstddef>
+#include <fstream>
+#include <sstream>
+#include <stdexcept>
+#include <string>
+
+void LifetimeStats::record(Ending ending, std::size_t fruit_eaten, std::size_t length) {
+  games++;
+  fruit += fruit_eaten;
+  best_length = std::max(best_length, length);
+
+  switch (ending) {
+  case Ending::Collision:
+    collisions++;
+    break;
+  case Ending::OutOfBounds:
+    out_of_bounds++;
+    break;
+  case Ending::Wall:
+    walls++;
+    break;
+  case Ending::Quit:
+  case Ending::Won:
+  case Ending::Error:
+    break;
+  }
+}
+
+std::string LifetimeStats::summary() const {
+  return std::to_string(games) + " games, " + std::to_string(fruit) + " fruit, best length " +
+         std::to_string(best_length) + "\n" + std::to_string(collisions) + " collisions, " +
+         std::to_string(out_of_bounds) + " off the grid, " + std::to_string(walls) + " walls";
+}
+
+LifetimeStats load_stats(const std::string &path) {
+  std::ifstream file(path);
+  if (!file)
+    return {};
+
+  std::ostringstream text;
+  text << file.rdbuf();
+
+  try {
+    const auto value = json::parse(text.str());
+
+    LifetimeStats stats;
+    stats.games = value["games"].to_size();
+    stats.fruit = value["fruit"].to_size();
+    stats.collisions = value["collisions"].to_size();
+    stats.out_of_bounds = value["out_of_bounds"].to_size();
+    stats.walls = value["walls"].to_size();
+    stats.best_length = value["best_length"].to_size();
+
+    return stats;
+  } catch (std::runtime_error const &) {
+    return {};
+  }
+}
+
+void save_stats(const LifetimeStats &stats, const std::string &path) {
+  std::ofstream file(path);
+  if (!file)
+    throw std::runtime_error("couldn't write to " + path);
+
+  file << "{\n  \"games\": " << stats.games << ",\n  \"fruit\": " << stats.fruit
+       << ",\n  \"collisions\": " << stats.collisions
+       << ",\n  \"out_of_bounds\": " << stats.out_of_bounds << ",\n  \"walls\": " << stats.walls
+       << ",\n  \"best_length\": " << stats.best_length << "\n}\n";
+}
@@ -0,0 +1,31 @@
+#pragma once
+
+#include <cstddef>
+#include <cstdint>
+#include <string>
+
+// What a game came to an end by. `Error` is for a game that found itself in a state it should
+// never have got into, and stopped there.
+enum class Ending : std::uint8_t { Collision, OutOfBounds, Wall, Quit, Won, Error };
+
+// Totals over every game ever played, kept in a small JSON file between runs.
+struct LifetimeStats {
+  std::size_t games = 0;
+  std::size_t fruit = 0;
+  // Deaths, by what the head ran into. Quitting isn't one, and neither is a game that broke.
+  std::size_t collisions = 0;
+  std::size_t out_of_bounds = 0;
+  std::size_t walls = 0;
+  std::size_t best_length = 0;
+
+  void record(Ending ending, std::size_t fruit_eaten, std::size_t length);
+
+  // A line or two for the title screen.
+  std::string summary() const;
+};
+
+// A missing or unreadable file just starts the totals over.
+LifetimeStats load_stats(const std::string &path);
+
+// Throws `std::runtime_error` if the file can't be written.
+void save_stats(const LifetimeStats &stats, const std::string &path);
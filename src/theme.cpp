@@ -0,0 +1,31 @@
+#include "theme.hpp"
+
+#include <SFML/Graphics.hpp>
+
+namespace theme {
+  const Theme classic = {
+    sf::Color::White,
+    sf::Color(0xF2, 0xF2, 0xF2),
+    sf::Color(0xFF, 0xF5, 0xB0),
+    sf::Color::Green,
+    sf::Color::Green,
+    sf::Color(0x40, 0x40, 0x40),
+    {sf::Color::Red, sf::Color::Blue, sf::Color(0xFF, 0xA5, 0x00)},
+  };
+
+  const Theme high_contrast = {
+    sf::Color::Black,
+    sf::Color(0x1A, 0x1A, 0x1A),
+    sf::Color(0x50, 0x50, 0x50),
+    sf::Color(0xA0, 0xA0, 0xA0),
+    sf::Color(0x00, 0xFF, 0x00),
+    sf::Color::White,
+    {sf::Color::Yellow, sf::Color(0xFF, 0xA5, 0x00)},
+  };
+
+  static const Theme *active = &classic;
+
+  const Theme &current() { return *active; }
+
+  void set(const Theme &theme) { active = &theme; }
+} // namespace theme
@@ -0,0 +1,35 @@
+#pragma once
+
+#include <SFML/Graphics.hpp>
+#include <vector>
+
+// The colours the board is drawn in.
+struct Theme {
+  sf::Color background;
+  // The faint tint of every other block in checkerboard mode.
+  sf::Color checker;
+  // What the background lights up in when a fruit is eaten.
+  sf::Color flash;
+  // The border around the grid and the outlines of vacant blocks.
+  sf::Color lines;
+  sf::Color snake;
+  sf::Color wall;
+  // Ordinary fruit get one of these at random.
+  std::vector<sf::Color> fruit;
+};
+
+namespace theme {
+  // Green on white.
+  extern const Theme classic;
+
+  // Bright colours on black for low vision. Against the background the walls come in at 21:1, the
+  // fruit at 19.6:1 (yellow) and 10.6:1 (orange), the snake at 15.3:1 and the grid lines at 8:1,
+  // all well above the 4.5:1 WCAG asks of text.
+  extern const Theme high_contrast;
+
+  // Whichever of the above everything is currently drawn in; `classic` to begin with.
+  const Theme &current();
+
+  // Switches to one of the presets above.
+  void set(const Theme &theme);
+} // namespace theme
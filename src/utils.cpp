@@ -0,0 +1,399 @@
+#include "utils.hpp"
+#include "snake.hpp"
+#include "theme.hpp"
+
+#include <SFML/Graphics.hpp>
+#include <algorithm>
+#include <cstddef>
+#include <cstdint>
+#include <cstdlib>
+#include <iostream>
+#include <memory>
+#include <optional>
+#include <random>
+#include <stdexcept>
+#include <string>
+#include <unordered_set>
+#include <vector>
+
+namespace randomiser {
+  static std::random_device source;
+  static std::uint32_t current_seed = source();
+  static std::mt19937 generator(current_seed);
+
+  std::size_t gen(std::size_t min, std::size_t max) {
+    auto dist = std::uniform_int_distribution<std::size_t>(min, max);
+    return dist(generator);
+  }
+
+  std::uint32_t seed() { return current_seed; }
+
+  void seed(std::uint32_t value) {
+    current_seed = value;
+    generator.seed(value);
+  }
+} // namespace randomiser
+
+sf::Color gen_fruit_colour() {
+  const auto &colours = theme::current().fruit;
+
+  return colours[randomiser::gen(0, colours.size() - 1)];
+}
+
+// Any of the theme's fruit colours that isn't on the board yet, or any at all once they all are.
+static sf::Color unused_fruit_colour(const Grid &grid) {
+  std::vector<sf::Color> unused;
+
+  const auto fruit = grid.fruit_positions();
+
+  for (const auto colour : theme::current().fruit) {
+    if (std::none_of(fruit.begin(), fruit.end(),
+                     [&](auto pos) { return grid[pos].colour() == colour; }))
+      unused.push_back(colour);
+  }
+
+  if (unused.empty())
+    return gen_fruit_colour();
+
+  return unused[randomiser::gen(0, unused.size() - 1)];
+}
+
+sf::Color fruit_colour(FruitKind kind) {
+  switch (kind) {
+  case FruitKind::Reverse:
+    return sf::Color::Magenta;
+  case FruitKind::Freeze:
+    return sf::Color::Cyan;
+  case FruitKind::Bonus:
+    return sf::Color(0xFF, 0xD7, 0x00);
+  case FruitKind::Normal:
+    break;
+  }
+
+  return gen_fruit_colour();
+}
+
+std::size_t get_block_randomly(const Grid &grid) {
+  // Guessing is quick while the board is mostly empty but can take very long once it's nearly
+  // full, so after a few misses count off a random vacant block instead.
+  static constexpr std::size_t max_attempts = 32;
+
+  for (std::size_t attempt = 0; attempt < max_attempts; attempt++) {
+    auto pos = randomiser::gen(0, grid.len() - 1);
+
+    if (!is_occupied(grid[pos].type()))
+      return pos;
+  }
+
+  auto skip = randomiser::gen(0, grid.free_count() - 1);
+
+  for (std::size_t pos = 0; pos < grid.len(); pos++) {
+    if (!is_occupied(grid[pos].type()) && skip-- == 0)
+      return pos;
+  }
+
+  throw std::logic_error("no vacant block to pick");
+}
+
+std::optional<std::size_t> RandomSpawner::pick(const Grid &grid, const Snake &) {
+  if (grid.free_count() == 0)
+    return std::nullopt;
+
+  return get_block_randomly(grid);
+}
+
+std::optional<std::size_t> ReachableSpawner::pick(const Grid &grid, const Snake &snake) {
+  std::vector<std::size_t> candidates;
+
+  for (const auto cell : grid.reachable_from(grid.to_index(snake.head()))) {
+    if (!is_occupied(grid[cell].type()))
+      candidates.push_back(cell);
+  }
+
+  if (candidates.empty())
+    return std::nullopt;
+
+  // The set's order isn't stable, which would make the pick depend on more than the RNG.
+  std::sort(candidates.begin(), candidates.end());
+
+  return candidates[randomiser::gen(0, candidates.size() - 1)];
+}
+
+std::optional<std::size_t> SequenceSpawner::pick(const Grid &grid, const Snake &snake) {
+  while (next < cells.size()) {
+    const auto pos = cells[next++];
+
+    if (pos.x < grid.horizontal() && pos.y < grid.vertical() &&
+        !is_occupied(grid[pos].type()))
+      return grid.to_index(pos);
+
+    std::cerr << "fruit sequence: " << to_string(sf::Vector2i(pos))
+              << " is off the grid or taken, skipping it\n";
+  }
+
+  over = true;
+
+  if (after)
+    return after->pick(grid, snake);
+
+  return std::nullopt;
+}
+
+std::optional<std::size_t> ClearOfHeadSpawner::pick(const Grid &grid, const Snake &snake) {
+  static constexpr std::size_t attempts = 16;
+
+  const auto head = grid.to_index(snake.head());
+  std::optional<std::size_t> pos;
+
+  for (std::size_t attempt = 0; attempt < attempts; attempt++) {
+    pos = inner.pick(grid, snake);
+
+    if (!pos || grid.manhattan(*pos, head) > clearance)
+      break;
+  }
+
+  return pos;
+}
+
+std::unique_ptr<FruitSpawner> make_spawner(const Config &config) {
+  std::unique_ptr<FruitSpawner> random;
+  if (config.fair_spawn) {
+    random = std::make_unique<ReachableSpawner>();
+  } else {
+    random = std::make_unique<RandomSpawner>();
+  }
+
+  if (config.fruit_sequence.empty())
+    return random;
+
+  return std::make_unique<SequenceSpawner>(
+    config.fruit_sequence, config.fruit_sequence_random ? std::move(random) : nullptr);
+}
+
+bool spawn_fruit(Grid &grid, const Snake &snake, const Config &config, FruitSpawner &spawner) {
+  const auto pick = spawner.pick(grid, snake);
+  if (!pick)
+    return false;
+
+  const auto pos = *pick;
+
+  // Laid out beforehand, so nothing about them is left to chance.
+  if (spawner.sequenced()) {
+    grid.place_fruit(pos);
+    return true;
+  }
+
+  auto kind = FruitKind::Normal;
+
+  if (config.chaos && randomiser::gen(0, 4) == 0) {
+    kind = FruitKind::Reverse;
+  } else if (config.freeze && randomiser::gen(0, 5) == 0) {
+    kind = FruitKind::Freeze;
+  }
+
+  const auto colour = kind == FruitKind::Normal && config.distinct_fruit_colours
+                        ? unused_fruit_colour(grid)
+                        : fruit_colour(kind);
+
+  grid.set_type(pos, BlockType::OccupiedFruit);
+  grid.set_fruit(pos, kind);
+  grid.set_colour(pos, colour);
+
+  return true;
+}
+
+std::vector<std::pair<std::size_t, std::size_t>> attract_fruit(Grid &grid, const Snake &snake) {
+  const auto head = snake.head();
+
+  std::vector<std::pair<std::size_t, std::size_t>> moved;
+
+  const auto fruit = grid.fruit_positions();
+
+  for (const auto pos : fruit) {
+    const auto xy = grid.to_xy(pos);
+
+    const auto dx = int(head.x) - int(xy.x);
+    const auto dy = int(head.y) - int(xy.y);
+
+    auto towards = Direction::None;
+    if (std::abs(dx) >= std::abs(dy) && dx != 0) {
+      towards = dx < 0 ? Direction::Left : Direction::Right;
+    } else if (dy != 0) {
+      towards = dy < 0 ? Direction::Up : Direction::Down;
+    }
+
+    const auto next = grid.neighbour(pos, towards, false);
+
+    if (towards != Direction::None && next && !is_occupied(grid[*next].type())) {
+      grid.move_fruit(pos, *next);
+      moved.emplace_back(pos, *next);
+    }
+  }
+
+  return moved;
+}
+
+std::vector<bool> reachable(const Grid &grid, std::size_t start,
+                            const std::vector<BlockType> &blocked, bool wrap) {
+  return reachable_if(
+    grid, start,
+    [&](std::size_t pos) {
+      return std::find(blocked.begin(), blocked.end(), grid[pos].type()) != blocked.end();
+    },
+    wrap);
+}
+
+std::unordered_set<std::size_t> Grid::reachable_from(std::size_t start) const {
+  const auto seen = reachable(*this, start, {BlockType::Wall});
+
+  std::unordered_set<std::size_t> cells;
+  for (std::size_t i = 0; i < seen.size(); i++) {
+    if (seen[i])
+      cells.insert(i);
+  }
+
+  return cells;
+}
+
+std::size_t safe_space(const Snake &snake, const Grid &grid, Direction direct) {
+  // Against the way the snake last moved, like `Snake::move`, not wherever it's been turned since.
+  if (snake.heading() != Direction::None && direct == opposite(snake.heading()))
+    return 0;
+
+  const auto head = grid.to_index(snake.head());
+  const auto next = grid.neighbour(head, direct, false);
+
+  if (!next || grid[*next].type() == BlockType::Wall)
+    return 0;
+
+  const bool eats = grid[*next].type() == BlockType::OccupiedFruit;
+
+  std::optional<std::size_t> vacated;
+  if (!eats) {
+    vacated = snake.body().empty() ? head : grid.to_index(snake.body().back());
+  }
+
+  if (grid[*next].type() == BlockType::OccupiedSnake && (next != vacated || !snake.tail_safe()))
+    return 0;
+
+  const auto seen = reachable_if(grid, *next, [&](std::size_t pos) {
+    const auto type = grid[pos].type();
+
+    return type == BlockType::Wall || (type == BlockType::OccupiedSnake && pos != vacated);
+  });
+
+  return std::size_t(std::count(seen.begin(), seen.end(), true));
+}
+
+// Whether the snake could still get its head round to its tail after moving in `direct`, which
+// means it can always carry on by following it. Only for moves `safe_space` finds room for.
+static bool reaches_tail(const Snake &snake, const Grid &grid, Direction direct) {
+  const auto head = grid.to_index(snake.head());
+  const auto next = *grid.neighbour(head, direct, false);
+
+  const bool eats = grid[next].type() == BlockType::OccupiedFruit;
+
+  // The last bite fills the grid, and there's nowhere left that it would need to get to.
+  if (eats && grid.free_count() == 0 && grid.fruit_count() == 1)
+    return true;
+
+  // Where the snake is after the move, from the new head back, the tail staying put if it eats.
+  std::vector<std::size_t> after{next};
+  const auto cells = snake.cells();
+  const auto kept = eats ? cells.size() : cells.size() - 1;
+
+  for (std::size_t i = 0; i < kept; i++) {
+    after.push_back(grid.to_index(cells[i]));
+  }
+
+  const auto tail = after.back();
+  if (tail == next)
+    return true;
+
+  std::vector<bool> body(grid.len(), false);
+  for (const auto pos : after) {
+    body[pos] = pos != tail;
+  }
+
+  const auto seen = reachable_if(grid, next, [&](std::size_t pos) {
+    return body[pos] || grid[pos].type() == BlockType::Wall;
+  });
+
+  return seen[tail];
+}
+
+Direction autopilot(const Snake &snake, const Grid &grid) {
+  const auto fruit = grid.fruit_positions();
+
+  const auto head = grid.to_index(snake.head());
+
+  auto roomiest = Direction::None;
+  std::size_t most_room = 0;
+
+  auto nearest = Direction::None;
+  auto nearest_distance = grid.len() * 2;
+
+  for (const auto direct : all_directions) {
+    const auto room = safe_space(snake, grid, direct);
+
+    if (room > most_room) {
+      most_room = room;
+      roomiest = direct;
+    }
+
+    if (room == 0 || !reaches_tail(snake, grid, direct))
+      continue;
+
+    // Rooms above zero guarantee the neighbour exists.
+    const auto next = *grid.neighbour(head, direct, false);
+
+    if (nearest == Direction::None) {
+      nearest = direct;
+    }
+
+    for (const auto pos : fruit) {
+      if (grid.manhattan(next, pos) < nearest_distance) {
+        nearest_distance = grid.manhattan(next, pos);
+        nearest = direct;
+      }
+    }
+  }
+
+  if (nearest != Direction::None)
+    return nearest;
+  if (roomiest != Direction::None)
+    return roomiest;
+
+  // Nowhere to go; carry on and take it like a snake.
+  return snake.direction() == Direction::None ? Direction::Right : snake.direction();
+}
+
+std::string to_ascii_map(const Grid &grid, const Snake &snake) {
+  std::string map;
+
+  for (std::size_t y = 0; y < grid.vertical(); y++) {
+    for (std::size_t x = 0; x < grid.horizontal(); x++) {
+      const auto pos = sf::Vector2u(x, y);
+
+      switch (grid[pos].type()) {
+      case BlockType::Vacant:
+      case BlockType::Painted:
+        map += '.';
+        break;
+      case BlockType::OccupiedSnake:
+        map += pos == snake.head() ? '@' : 'o';
+        break;
+      case BlockType::OccupiedFruit:
+        map += '*';
+        break;
+      case BlockType::Wall:
+        map += '#';
+        break;
+      }
+    }
+
+    map += '\n';
+  }
+
+  return map;
+}
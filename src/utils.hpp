@@ -0,0 +1,165 @@
+#pragma once
+
+#include "grid.hpp"
+
+#include <SFML/Graphics.hpp>
+#include <algorithm>
+#include <cstddef>
+#include <cstdint>
+#include <deque>
+#include <memory>
+#include <optional>
+#include <string>
+#include <utility>
+#include <vector>
+
+class Snake;
+struct Config;
+
+namespace randomiser {
+  // Uniformly distributed between `min` and `max`, both included.
+  std::size_t gen(std::size_t min, std::size_t max);
+
+  // The seed the generator was last (re)started from, so a game can be reproduced.
+  std::uint32_t seed();
+
+  void seed(std::uint32_t value);
+} // namespace randomiser
+
+sf::Color gen_fruit_colour();
+
+// Special fruit always look the same so they can be told apart at a glance; only ordinary fruit
+// get a random colour from `gen_fruit_colour`.
+sf::Color fruit_colour(FruitKind kind);
+
+// Picks a random vacant block. There has to be at least one.
+std::size_t get_block_randomly(const Grid &grid);
+
+// Decides where fruit go. Each strategy is asked for a vacant block for the next one, and gives
+// none if it hasn't got one.
+class FruitSpawner {
+public:
+  virtual ~FruitSpawner() = default;
+
+  virtual std::optional<std::size_t> pick(const Grid &grid, const Snake &snake) = 0;
+
+  // Whether fruit are still laid out as listed beforehand, one at a time as each goes, rather than
+  // whenever the game wants more. Only sequences are.
+  virtual bool sequenced() const noexcept { return false; }
+
+  // How far a strategy that works through a list has got, for taking it back there later. The
+  // others have nowhere to be.
+  virtual std::size_t position() const noexcept { return 0; }
+  virtual void set_position(std::size_t) noexcept {}
+};
+
+// Any vacant block, all equally likely.
+class RandomSpawner : public FruitSpawner {
+public:
+  std::optional<std::size_t> pick(const Grid &grid, const Snake &snake) override;
+};
+
+// Only blocks the snake can actually get to, for `Config::fair_spawn`.
+class ReachableSpawner : public FruitSpawner {
+public:
+  std::optional<std::size_t> pick(const Grid &grid, const Snake &snake) override;
+};
+
+// The blocks of `Config::fruit_sequence` in order, skipping any that are off the grid or taken
+// by then. Once they run out, picks come from `after`, or there are none without it.
+class SequenceSpawner : public FruitSpawner {
+  std::vector<sf::Vector2u> cells;
+  std::size_t next = 0;
+  // Set by the first pick that found nothing left.
+  bool over = false;
+  std::unique_ptr<FruitSpawner> after;
+
+public:
+  explicit SequenceSpawner(std::vector<sf::Vector2u> cells,
+                           std::unique_ptr<FruitSpawner> after = nullptr)
+    : cells(std::move(cells)), after(std::move(after)) {}
+
+  std::optional<std::size_t> pick(const Grid &grid, const Snake &snake) override;
+
+  // Without anything to hand over to, a sequence that has run out still is one, just empty.
+  bool sequenced() const noexcept override { return !over || !after; }
+
+  // One past the end once it has run out.
+  std::size_t position() const noexcept override { return over ? cells.size() + 1 : next; }
+  void set_position(std::size_t position) noexcept override {
+    next = std::min(position, cells.size());
+    over = position > cells.size();
+  }
+};
+
+// Whatever `inner` picks, as long as it's more than `clearance` blocks from the head. After a few
+// picks that aren't, the last one will do, so a crowded board still gets its fruit.
+class ClearOfHeadSpawner : public FruitSpawner {
+  FruitSpawner &inner;
+  std::size_t clearance;
+
+public:
+  ClearOfHeadSpawner(FruitSpawner &inner, std::size_t clearance)
+    : inner(inner), clearance(clearance) {}
+
+  std::optional<std::size_t> pick(const Grid &grid, const Snake &snake) override;
+};
+
+// A `SequenceSpawner` for `Config::fruit_sequence` if there is one, handing over to random fruit
+// with `Config::fruit_sequence_random`. Random fruit come from a `ReachableSpawner` with
+// `Config::fair_spawn` and a `RandomSpawner` otherwise.
+std::unique_ptr<FruitSpawner> make_spawner(const Config &config);
+
+// Puts a fruit down wherever `spawner` picks, of a kind and colour that `config` decides, or just
+// an ordinary one for a sequence. False if there was nowhere for it to go.
+bool spawn_fruit(Grid &grid, const Snake &snake, const Config &config, FruitSpawner &spawner);
+
+// Moves every fruit one block closer to the snake's head, along whichever axis it's further away
+// on, unless something is in the way. Returns where each fruit that moved was and went.
+std::vector<std::pair<std::size_t, std::size_t>> attract_fruit(Grid &grid, const Snake &snake);
+
+// Breadth-first search from `start` over the four neighbours of each block, never entering a block
+// for which `is_blocked(pos)` holds. The start itself is always reachable.
+template <typename Blocked>
+std::vector<bool> reachable_if(const Grid &grid, std::size_t start, Blocked is_blocked,
+                               bool wrap = false) {
+  std::vector<bool> seen(grid.len(), false);
+  std::deque<std::size_t> pending{start};
+
+  seen[start] = true;
+
+  while (!pending.empty()) {
+    const auto pos = pending.front();
+    pending.pop_front();
+
+    for (const auto direct : all_directions) {
+      const auto next = grid.neighbour(pos, direct, wrap);
+
+      if (next && !seen[*next] && !is_blocked(*next)) {
+        seen[*next] = true;
+        pending.push_back(*next);
+      }
+    }
+  }
+
+  return seen;
+}
+
+// Which blocks can be reached from `start` without entering a block of one of the `blocked` types.
+std::vector<bool> reachable(const Grid &grid, std::size_t start,
+                            const std::vector<BlockType> &blocked, bool wrap = false);
+
+// How much room the snake would have after moving in `direct`: the number of blocks reachable from
+// where the head ends up, with walls and the body as obstacles. The tail doesn't count as one since
+// it moves out of the way on the same tick, unless the move eats a fruit, though only with
+// `Config::tail_safe` can the head go straight into it. Moves that would end the game have no room
+// at all.
+std::size_t safe_space(const Snake &snake, const Grid &grid, Direction direct);
+
+// Picks the snake's next direction on its own: towards the nearest fruit, as long as the head can
+// still get round to the tail afterwards, and otherwise wherever there is the most room.
+Direction autopilot(const Snake &snake, const Grid &grid);
+
+// One character per block, one line per row: `#` wall, `*` fruit, `@` the snake's head, `o` the
+// rest of its body and `.` vacant.
+std::string to_ascii_map(const Grid &grid, const Snake &snake);
@@ -0,0 +1,36 @@
+// The smallest rectangle round the whole snake.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <vector>
+
+namespace {
+  sf::Rect<unsigned> box_of(sf::Vector2u head, const std::vector<sf::Vector2u> &body) {
+    Config config;
+    config.horizontal = 9;
+    config.vertical = 9;
+    config.keep_records = false;
+
+    Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+    Snake snake(grid, config);
+
+    lay_out(grid, snake, head, body);
+
+    return snake.bounding_box();
+  }
+} // namespace
+
+int main() {
+  // Just the head.
+  CHECK(box_of({4, 4}, {}) == sf::Rect<unsigned>(4, 4, 1, 1));
+
+  // Straight along a row, and down a column with the head at the bottom.
+  CHECK(box_of({5, 2}, {{4, 2}, {3, 2}, {2, 2}}) == sf::Rect<unsigned>(2, 2, 4, 1));
+  CHECK(box_of({6, 7}, {{6, 6}, {6, 5}}) == sf::Rect<unsigned>(6, 5, 1, 3));
+
+  // An L, the head at the end of the short side, reaching right to the corner of the grid.
+  CHECK(box_of({2, 8}, {{1, 8}, {0, 8}, {0, 7}, {0, 6}, {0, 5}}) ==
+        sf::Rect<unsigned>(0, 5, 3, 4));
+  // And the other way up, with the head in the middle of the box's top.
+  CHECK(box_of({3, 1}, {{3, 2}, {3, 3}, {4, 3}, {5, 3}}) == sf::Rect<unsigned>(3, 1, 3, 3));
+}
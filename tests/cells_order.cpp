@@ -0,0 +1,49 @@
+// `Snake::cells` listing the head first and then every segment back to the tip of the tail, as the
+// snake grows and winds about.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <vector>
+
+int main() {
+  randomiser::seed(1);
+
+  Config config;
+  config.horizontal = 7;
+  config.vertical = 7;
+  config.keep_records = false;
+
+  Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+  Snake snake(grid, config);
+  lay_out(grid, snake, sf::Vector2u(1, 1));
+
+  using Cells = std::vector<sf::Vector2u>;
+  CHECK((snake.cells() == Cells{{1, 1}}));
+
+  // Right across a row of fruit growing all the way, then down and back left without any.
+  for (const unsigned x : {2, 3, 4}) {
+    CHECK(grid.place_fruit(grid.to_index(sf::Vector2u(x, 1))));
+  }
+
+  snake.set_direction(Direction::Right);
+  CHECK(snake.move());
+  CHECK((snake.cells() == Cells{{2, 1}, {1, 1}}));
+  CHECK(snake.move());
+  CHECK(snake.move());
+  CHECK((snake.cells() == Cells{{4, 1}, {3, 1}, {2, 1}, {1, 1}}));
+
+  snake.set_direction(Direction::Down);
+  CHECK(!snake.move());
+  CHECK((snake.cells() == Cells{{4, 2}, {4, 1}, {3, 1}, {2, 1}}));
+
+  snake.set_direction(Direction::Left);
+  CHECK(!snake.move());
+  CHECK(!snake.move());
+  CHECK((snake.cells() == Cells{{2, 2}, {3, 2}, {4, 2}, {4, 1}}));
+
+  // The head and the body on their own agree with it.
+  const auto cells = snake.cells();
+  CHECK(cells.front() == snake.head());
+  CHECK((Cells(cells.begin() + 1, cells.end()) == snake.body()));
+  CHECK(snake.len() == cells.size());
+}
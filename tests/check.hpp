@@ -0,0 +1,56 @@
+#pragma once
+
+#include "snek.hpp"
+
+#include <cstdlib>
+#include <iostream>
+#include <utility>
+#include <vector>
+
+// What the tests under `tests/` share. Each is a program of its own that exits with 1 at the first
+// check that fails, saying which one.
+
+#define CHECK(condition)                                                                        \
+  do {                                                                                          \
+    if (!(condition)) {                                                                         \
+      std::cerr << __FILE__ << ":" << __LINE__ << ": check failed: " #condition "\n";           \
+      std::exit(EXIT_FAILURE);                                                                  \
+    }                                                                                           \
+  } while (false)
+
+// Fails unless `statement` throws `exception`, or something derived from it.
+#define CHECK_THROWS(statement, exception)                                                      \
+  do {                                                                                          \
+    bool thrown = false;                                                                        \
+    try {                                                                                       \
+      statement;                                                                                \
+    } catch (exception const &) {                                                               \
+      thrown = true;                                                                            \
+    }                                                                                           \
+    if (!thrown) {                                                                              \
+      std::cerr << __FILE__ << ":" << __LINE__ << ": " #statement " didn't throw " #exception  \
+                   "\n";                                                                        \
+      std::exit(EXIT_FAILURE);                                                                  \
+    }                                                                                           \
+  } while (false)
+
+// Takes the snake off `grid` wherever it is and lays it out again from `head` back along `body`,
+// marking every block, however they're laid out. `Snake::restore` leaves the grid to the caller.
+inline void lay_out(Grid &grid, Snake &snake, sf::Vector2u head,
+                    std::vector<sf::Vector2u> body = {}, Direction direct = Direction::None) {
+  for (std::size_t i = 0; i < grid.len(); i++) {
+    if (grid[i].type() == BlockType::OccupiedSnake) {
+      grid.set_type(i, BlockType::Vacant);
+      grid.set_colour(i, theme::current().lines);
+    }
+  }
+
+  grid.set_type(head, BlockType::OccupiedSnake);
+  grid.set_colour(head, theme::current().snake);
+  for (const auto pos : body) {
+    grid.set_type(pos, BlockType::OccupiedSnake);
+    grid.set_colour(pos, theme::current().snake);
+  }
+
+  snake.restore(head, std::move(body), direct);
+}
@@ -0,0 +1,52 @@
+// The colour helpers, at the ends of their ranges and there and back again.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <cmath>
+#include <stdexcept>
+
+int main() {
+  // Hex, in every form it's read in.
+  CHECK(colour::from_hex("#00ff7f") == sf::Color(0x00, 0xFF, 0x7F));
+  CHECK(colour::from_hex("00FF7F") == sf::Color(0x00, 0xFF, 0x7F));
+  CHECK(colour::from_hex("#0f8") == sf::Color(0x00, 0xFF, 0x88));
+  CHECK(colour::from_hex("#12345678") == sf::Color(0x12, 0x34, 0x56, 0x78));
+
+  for (const auto *bad : {"", "#", "#12", "#1234", "#12345g", "#123456789"}) {
+    CHECK_THROWS(colour::from_hex(bad), std::invalid_argument);
+  }
+
+  CHECK(colour::to_hex(sf::Color(0x00, 0xFF, 0x7F)) == "#00ff7f");
+  CHECK(colour::to_hex(sf::Color(0x12, 0x34, 0x56, 0x78)) == "#12345678");
+
+  for (const auto &hex : {"#000000", "#ffffff", "#a1b2c3", "#a1b2c300"}) {
+    CHECK(colour::to_hex(colour::from_hex(hex)) == hex);
+  }
+
+  // HSV from the primaries round to where it started.
+  CHECK(colour::from_hsv(0.0f, 1.0f, 1.0f) == sf::Color::Red);
+  CHECK(colour::from_hsv(120.0f, 1.0f, 1.0f) == sf::Color::Green);
+  CHECK(colour::from_hsv(240.0f, 1.0f, 1.0f) == sf::Color::Blue);
+  CHECK(colour::from_hsv(360.0f, 1.0f, 1.0f) == sf::Color::Red);
+  CHECK(colour::from_hsv(-120.0f, 1.0f, 1.0f) == sf::Color::Blue);
+  CHECK(colour::from_hsv(77.0f, 0.0f, 1.0f) == sf::Color::White);
+  CHECK(colour::from_hsv(77.0f, 1.0f, 0.0f) == sf::Color::Black);
+
+  const auto clear = sf::Color(0, 0, 0, 0);
+  CHECK(colour::lerp(clear, sf::Color::White, 0.0f) == clear);
+  CHECK(colour::lerp(clear, sf::Color::White, 1.0f) == sf::Color::White);
+  CHECK(colour::lerp(clear, sf::Color::White, 0.5f) == sf::Color(128, 128, 128, 128));
+  CHECK(colour::lerp(clear, sf::Color::White, -1.0f) == clear);
+  CHECK(colour::lerp(clear, sf::Color::White, 2.0f) == sf::Color::White);
+
+  CHECK(colour::luminance(sf::Color::Black) == 0.0f);
+  CHECK(std::abs(colour::luminance(sf::Color::White) - 1.0f) < 1e-5f);
+  CHECK(colour::luminance(sf::Color::Green) > colour::luminance(sf::Color::Red));
+  CHECK(colour::luminance(sf::Color::Red) > colour::luminance(sf::Color::Blue));
+
+  // Text on either theme's background.
+  CHECK(colour::contrasting(theme::classic.background) == sf::Color::Black);
+  CHECK(colour::contrasting(theme::high_contrast.background) == sf::Color::White);
+  CHECK(colour::contrasting(sf::Color(0x20, 0x20, 0x60)) == sf::Color::White);
+  CHECK(colour::contrasting(sf::Color::Yellow) == sf::Color::Black);
+}
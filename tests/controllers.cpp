@@ -0,0 +1,128 @@
+// Each of the ways a snake gets steered, on its own and in charge of a game.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <memory>
+#include <vector>
+
+namespace {
+  Scenario open_board(const Config &config, sf::Vector2u head) {
+    Scenario scenario;
+    scenario.horizontal = config.horizontal;
+    scenario.vertical = config.vertical;
+    scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+    scenario.head = head;
+    scenario.direction = Direction::Right;
+
+    return scenario;
+  }
+} // namespace
+
+int main() {
+  randomiser::seed(1);
+
+  Config config;
+  config.horizontal = 7;
+  config.vertical = 7;
+  config.keep_records = false;
+
+  Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+  Snake snake(grid, config);
+  lay_out(grid, snake, sf::Vector2u(3, 3));
+
+  // The player's turns come out in the order they went in, as many as there's room for.
+  {
+    HumanController human(2);
+    CHECK(!human.waiting());
+    CHECK(!human.last());
+    CHECK(!human.next_direction(snake, grid));
+
+    CHECK(human.push(Direction::Up));
+    CHECK(human.push(Direction::Left));
+    CHECK(!human.push(Direction::Down));
+    CHECK(human.last() == Direction::Left);
+
+    CHECK(human.next_direction(snake, grid) == Direction::Up);
+    CHECK(human.next_direction(snake, grid) == Direction::Left);
+    CHECK(!human.next_direction(snake, grid));
+
+    CHECK(human.push(Direction::Down));
+    human.clear();
+    CHECK(!human.waiting());
+    CHECK(!human.next_direction(snake, grid));
+  }
+
+  // With no room at all, nothing gets through.
+  {
+    HumanController human(0);
+    CHECK(!human.push(Direction::Up));
+    CHECK(!human.waiting());
+  }
+
+  // The autopilot heads for the fruit, but not through a wall to get there.
+  {
+    AiController ai;
+
+    CHECK(grid.place_fruit(grid.to_index(sf::Vector2u(5, 3))));
+    CHECK(ai.next_direction(snake, grid) == Direction::Right);
+
+    grid.set_type(sf::Vector2u(4, 3), BlockType::Wall);
+    const auto around = ai.next_direction(snake, grid);
+    CHECK(around && around != Direction::Right);
+
+    grid.set_type(sf::Vector2u(4, 3), BlockType::Vacant);
+    grid.set_type(sf::Vector2u(5, 3), BlockType::Vacant);
+    CHECK(grid.place_fruit(grid.to_index(sf::Vector2u(3, 0))));
+    CHECK(ai.next_direction(snake, grid) == Direction::Up);
+  }
+
+  // A replay gives back exactly what was recorded, and then nothing.
+  {
+    ReplayController replay({Direction::Up, Direction::None, Direction::Left});
+    CHECK(!replay.finished());
+
+    CHECK(replay.next_direction(snake, grid) == Direction::Up);
+    CHECK(!replay.next_direction(snake, grid));
+    CHECK(replay.next_direction(snake, grid) == Direction::Left);
+
+    CHECK(replay.finished());
+    CHECK(!replay.next_direction(snake, grid));
+  }
+
+  // Given a game, a replay steers it move by move and the snake carries on once it runs out.
+  {
+    Game game(config, sf::Vector2u(300, 300));
+    CHECK(game.load_scenario(open_board(config, sf::Vector2u(1, 1))));
+    game.set_controller(std::make_unique<ReplayController>(
+      std::vector<Direction>{Direction::None, Direction::Down, Direction::None, Direction::Left}));
+
+    // Sets off.
+    game.advance(movement_interval);
+
+    std::vector<sf::Vector2u> heads;
+    for (std::size_t i = 0; i < 5; i++) {
+      game.advance(movement_interval);
+      heads.push_back(game.snake().head());
+    }
+
+    CHECK((heads == std::vector<sf::Vector2u>{{2, 1}, {2, 2}, {2, 3}, {1, 3}, {0, 3}}));
+  }
+
+  // And the autopilot eats what it's after.
+  {
+    auto scenario = open_board(config, sf::Vector2u(1, 1));
+    scenario.blocks[4 + 5 * scenario.horizontal] = BlockType::OccupiedFruit;
+
+    Game game(config, sf::Vector2u(300, 300));
+    CHECK(game.load_scenario(scenario));
+    game.set_controller(std::make_unique<AiController>());
+
+    for (std::size_t tick = 0; tick < 10 && game.score() == 0; tick++) {
+      game.advance(movement_interval);
+    }
+
+    CHECK(game.score() == 1);
+    CHECK(game.snake().head() == sf::Vector2u(4, 5));
+    CHECK(!game.over());
+  }
+}
@@ -0,0 +1,53 @@
+// The snake moving, eating and running into itself on a board where nothing is left to chance.
+#include "check.hpp"
+#include "snek.hpp"
+
+int main() {
+  randomiser::seed(1);
+
+  Config config;
+  config.horizontal = 10;
+  config.vertical = 10;
+  config.spawn_mode = SpawnMode::Center;
+  config.keep_records = false;
+
+  Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+  Snake snake(grid, config);
+
+  CHECK(snake.head() == sf::Vector2u(5, 5));
+  CHECK(snake.len() == 1);
+
+  // A row of fruit straight ahead, each eaten on the move into it.
+  for (unsigned x = 6; x < 10; x++) {
+    CHECK(grid.place_fruit(grid.to_index(sf::Vector2u(x, 5))));
+  }
+
+  snake.set_direction(Direction::Right);
+
+  for (std::size_t eaten = 1; eaten <= 4; eaten++) {
+    const auto fruit = snake.move();
+
+    CHECK(fruit == FruitKind::Normal);
+    CHECK(snake.len() == 1 + eaten);
+    CHECK(grid.fruit_count() == 4 - eaten);
+  }
+
+  CHECK(snake.head() == sf::Vector2u(9, 5));
+  CHECK(grid[sf::Vector2u(9, 5)].type() == BlockType::OccupiedSnake);
+
+  // Nothing left to eat, so the length holds.
+  snake.set_direction(Direction::Down);
+  CHECK(!snake.move());
+  CHECK(snake.len() == 5);
+
+  snake.set_direction(Direction::Left);
+  CHECK(!snake.move());
+  CHECK(snake.head() == sf::Vector2u(8, 6));
+
+  // Straight back up into the segment that has just come round the corner.
+  snake.set_direction(Direction::Up);
+  CHECK_THROWS(snake.move(), CollisionException);
+
+  // Nor can it turn back on itself instead.
+  CHECK_THROWS(snake.set_direction(Direction::Right), MotorException);
+}
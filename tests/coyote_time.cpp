@@ -0,0 +1,124 @@
+// A move into a wall or off the grid waiting `Config::coyote_time` for a turn away before it ends
+// the game.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <optional>
+
+namespace {
+  static constexpr float tick = 0.05f;
+
+  struct Played {
+    std::optional<Ending> ending;
+    // Ticks it took to end, if it did.
+    std::optional<std::size_t> ended_after;
+    std::size_t steps;
+    sf::Vector2u head;
+  };
+
+  // Next to the right hand edge and heading for it, or for a wall one block short of it, turning
+  // up after `turn_after` ticks if given. With `late_start`, the snake sets off heading down and
+  // only turns towards the edge on the first tick of the move.
+  Played play(float coyote_time, bool wall, std::optional<std::size_t> turn_after,
+              bool late_start = false) {
+    Config config;
+    config.horizontal = 7;
+    config.vertical = 7;
+    config.keep_records = false;
+    config.coyote_time = coyote_time;
+
+    Scenario scenario;
+    scenario.horizontal = config.horizontal;
+    scenario.vertical = config.vertical;
+    scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+    scenario.head = sf::Vector2u(wall ? 5 : 6, 3);
+    scenario.direction = late_start ? Direction::Down : Direction::Right;
+
+    if (wall) {
+      scenario.blocks[6 + 3 * scenario.horizontal] = BlockType::Wall;
+    }
+
+    Game game(config, sf::Vector2u(300, 300));
+    CHECK(game.load_scenario(scenario));
+
+    Played played{};
+    game.set_observer([&](const GameEvent &event) {
+      if (event.kind == GameEvent::Kind::Ended) {
+        played.ending = event.ending;
+      }
+    });
+
+    // Sets off.
+    game.advance(tick);
+    CHECK(game.state() == GameStates::InProgress);
+
+    for (std::size_t i = 1; i <= 20 && !game.over(); i++) {
+      if (late_start && i == 1) {
+        game.input(Direction::Right);
+      }
+      if (turn_after && i == *turn_after + 1) {
+        game.input(Direction::Up);
+      }
+
+      game.advance(tick);
+
+      if (game.over()) {
+        played.ended_after = i;
+      }
+    }
+
+    played.steps = game.step_count();
+    played.head = game.snake().head();
+
+    return played;
+  }
+} // namespace
+
+int main() {
+  randomiser::seed(1);
+
+  // Off by default, so the move happens as soon as it's due: a quarter of a second, five ticks.
+  for (const bool wall : {false, true}) {
+    const auto played = play(0.0f, wall, std::nullopt);
+
+    CHECK(played.ending == (wall ? Ending::Wall : Ending::OutOfBounds));
+    CHECK(played.ended_after && *played.ended_after <= 6);
+    CHECK(played.steps == 0);
+  }
+
+  // Held back two ticks longer, and still fatal when no turn comes.
+  for (const bool wall : {false, true}) {
+    const auto played = play(0.1f, wall, std::nullopt);
+
+    CHECK(played.ending == (wall ? Ending::Wall : Ending::OutOfBounds));
+    CHECK(played.ended_after && *played.ended_after >= 7 && *played.ended_after <= 8);
+  }
+
+  // A turn while it's held saves the snake, which then moves that way at once.
+  for (const bool wall : {false, true}) {
+    const auto played = play(0.1f, wall, 5);
+
+    CHECK(!played.ending);
+    CHECK(played.steps >= 1);
+    CHECK(played.head.x == (wall ? 5u : 6u));
+    CHECK(played.head.y < 3);
+  }
+
+  // The same when the snake had already turned towards the edge earlier in the move, which puts
+  // the turn away in line behind that one.
+  for (const bool wall : {false, true}) {
+    const auto played = play(0.1f, wall, 5, true);
+
+    CHECK(!played.ending);
+    CHECK(played.steps >= 1);
+    CHECK(played.head.x == (wall ? 5u : 6u));
+    CHECK(played.head.y < 3);
+  }
+
+  CHECK(play(0.1f, false, std::nullopt, true).ending == Ending::OutOfBounds);
+
+  // Too late, even with the grace period.
+  CHECK(play(0.1f, false, 9).ending == Ending::OutOfBounds);
+  // And without one, the same turn doesn't make it in time.
+  CHECK(play(0.0f, false, 5).ending == Ending::OutOfBounds);
+}
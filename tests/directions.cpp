@@ -0,0 +1,88 @@
+// The eight directions: how far each moves, which is opposite which, and the snake never turning
+// straight back on itself along a diagonal either.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <utility>
+
+int main() {
+  const std::pair<Direction, sf::Vector2i> deltas[] = {
+    {Direction::Left, {-1, 0}},     {Direction::Right, {1, 0}},    {Direction::Up, {0, -1}},
+    {Direction::Down, {0, 1}},      {Direction::UpLeft, {-1, -1}}, {Direction::UpRight, {1, -1}},
+    {Direction::DownLeft, {-1, 1}}, {Direction::DownRight, {1, 1}},
+  };
+
+  CHECK(to_pos(Direction::None) == sf::Vector2i(0, 0));
+  CHECK(opposite(Direction::None) == Direction::None);
+  CHECK(direction_of(sf::Vector2i(0, 0)) == Direction::None);
+
+  for (const auto &[direct, delta] : deltas) {
+    CHECK(to_pos(direct) == delta);
+    CHECK(direction_of(delta) == direct);
+    // Only the signs count.
+    CHECK(direction_of(delta * 3) == direct);
+
+    CHECK(opposite(opposite(direct)) == direct);
+    CHECK(opposite(direct) != direct);
+    CHECK(to_pos(opposite(direct)) == -delta);
+
+    CHECK(is_diagonal(direct) == (delta.x != 0 && delta.y != 0));
+  }
+
+  CHECK(diagonal(Direction::Up, Direction::Left) == Direction::UpLeft);
+  CHECK(diagonal(Direction::Down, Direction::Right) == Direction::DownRight);
+  CHECK(diagonal(Direction::Left, Direction::Up) == Direction::None);
+  CHECK(diagonal(Direction::Up, Direction::Up) == Direction::None);
+
+  Config config;
+  config.horizontal = 7;
+  config.vertical = 7;
+  config.keep_records = false;
+  config.diagonal = true;
+
+  Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+  Snake snake(grid, config);
+  lay_out(grid, snake, sf::Vector2u(2, 2));
+  CHECK(grid.place_fruit(grid.to_index(sf::Vector2u(3, 3))));
+
+  snake.set_direction(Direction::DownRight);
+  CHECK(snake.move() == FruitKind::Normal);
+  CHECK(snake.head() == sf::Vector2u(3, 3));
+  CHECK(snake.heading() == Direction::DownRight);
+
+  // Back along the diagonal is the one way that's refused; even the straight turns either side
+  // of it are fine.
+  CHECK_THROWS(snake.set_direction(Direction::UpLeft), MotorException);
+  CHECK(snake.direction() == Direction::DownRight);
+
+  for (const auto direct : {Direction::Up, Direction::Left, Direction::UpRight,
+                            Direction::DownLeft, Direction::Right, Direction::Down}) {
+    snake.set_direction(direct);
+  }
+
+  // Segments corner to corner are whole as far as a diagonal snake goes.
+  snake.set_direction(Direction::UpRight);
+  CHECK(!snake.move());
+  CHECK(snake.head() == sf::Vector2u(4, 2));
+  snake.validate();
+  CHECK_THROWS(snake.set_direction(Direction::DownLeft), MotorException);
+
+  // Without the mode on, a game doesn't take diagonals at all.
+  config.diagonal = false;
+
+  Scenario scenario;
+  scenario.horizontal = config.horizontal;
+  scenario.vertical = config.vertical;
+  scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+  scenario.head = sf::Vector2u(2, 2);
+  scenario.direction = Direction::Right;
+
+  Game game(config, sf::Vector2u(300, 300));
+  CHECK(game.load_scenario(scenario));
+
+  game.advance(movement_interval);
+  game.input(Direction::DownRight);
+  game.advance(movement_interval);
+
+  CHECK(game.snake().head() == sf::Vector2u(3, 2));
+}
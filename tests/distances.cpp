@@ -0,0 +1,14 @@
+// How far apart blocks are, which ones are next to each other and which can be got to at all.
+#include "check.hpp"
+#include "snek.hpp"
+
+int main() {
+  Grid grid(6, 4, grid_origin, sf::Vector2u(300, 300));
+
+  const auto at = [&](unsigned x, unsigned y) { return grid.to_index(sf::Vector2u(x, y)); };
+
+  CHECK(grid.manhattan(at(0, 0), at(0, 0)) == 0);
+  CHECK(grid.manhattan(at(0, 0), at(5, 3)) == 8);
+  CHECK(grid.manhattan(at(5, 3), at(0, 0)) == 8);
+  CHECK(grid.manhattan(at(4, 1), at(1, 2)) == 4);
+}
@@ -0,0 +1,40 @@
+// The score counts points, so a bonus fruit adds more than one, while the fruit eaten go up by one
+// a fruit whatever it was worth.
+#include "check.hpp"
+#include "snek.hpp"
+
+int main() {
+  randomiser::seed(1);
+
+  Config config;
+  config.horizontal = 9;
+  config.vertical = 5;
+  config.keep_records = false;
+
+  // A bonus fruit and then a normal one, straight ahead.
+  Scenario scenario;
+  scenario.horizontal = config.horizontal;
+  scenario.vertical = config.vertical;
+  scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+  scenario.blocks[1 + 2 * scenario.horizontal] = BlockType::OccupiedFruit;
+  scenario.blocks[2 + 2 * scenario.horizontal] = BlockType::OccupiedFruit;
+  scenario.head = sf::Vector2u(0, 2);
+  scenario.direction = Direction::Right;
+
+  Game game(config, sf::Vector2u(300, 300));
+  CHECK(game.load_scenario(scenario));
+  game.grid().set_fruit(game.grid().to_index(sf::Vector2u(1, 2)), FruitKind::Bonus);
+
+  // Sets off.
+  game.advance(movement_interval);
+  CHECK(game.score() == 0);
+  CHECK(game.eaten_count() == 0);
+
+  game.advance(movement_interval);
+  CHECK(game.score() == bonus_points);
+  CHECK(game.eaten_count() == 1);
+
+  game.advance(movement_interval);
+  CHECK(game.score() == bonus_points + 1);
+  CHECK(game.eaten_count() == 2);
+}
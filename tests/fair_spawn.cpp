@@ -0,0 +1,71 @@
+// Fruit only going where the snake can get to with `Config::fair_spawn`, with a corner of the
+// board walled off.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <unordered_set>
+
+int main() {
+  randomiser::seed(1);
+
+  Config config;
+  config.horizontal = 7;
+  config.vertical = 7;
+  config.keep_records = false;
+  config.fair_spawn = true;
+
+  Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+  Snake snake(grid, config);
+  lay_out(grid, snake, sf::Vector2u(1, 5));
+
+  // The top right two by two, behind an L of walls.
+  for (const auto pos : {sf::Vector2u(4, 0), sf::Vector2u(4, 1), sf::Vector2u(4, 2),
+                         sf::Vector2u(5, 2), sf::Vector2u(6, 2)}) {
+    grid.set_type(pos, BlockType::Wall);
+  }
+
+  std::unordered_set<std::size_t> pocket;
+  for (const auto pos :
+       {sf::Vector2u(5, 0), sf::Vector2u(6, 0), sf::Vector2u(5, 1), sf::Vector2u(6, 1)}) {
+    pocket.insert(grid.to_index(pos));
+  }
+
+  const auto reached = grid.reachable_from(grid.to_index(snake.head()));
+  CHECK(reached.size() == grid.len() - 5 - pocket.size());
+  for (const auto cell : pocket) {
+    CHECK(!reached.count(cell));
+  }
+
+  // Straight from the pocket, there's only the pocket.
+  CHECK(grid.reachable_from(grid.to_index(sf::Vector2u(6, 0))) == pocket);
+
+  // Nothing to stop a plain random pick from landing in there, over enough of them.
+  std::size_t unfair = 0;
+  RandomSpawner random;
+  for (std::size_t i = 0; i < 500; i++) {
+    unfair += pocket.count(*random.pick(grid, snake));
+  }
+  CHECK(unfair > 0);
+
+  auto fair = make_spawner(config);
+  for (std::size_t i = 0; i < 500; i++) {
+    CHECK(spawn_fruit(grid, snake, config, *fair));
+
+    const auto fruit = grid.fruit_positions();
+    CHECK(fruit.size() == 1);
+    CHECK(!pocket.count(fruit.front()));
+    CHECK(reached.count(fruit.front()));
+
+    grid.set_type(fruit.front(), BlockType::Vacant);
+  }
+
+  // Once everything the snake can reach is taken, there's nowhere fair left even with the pocket
+  // still empty.
+  for (const auto cell : reached) {
+    if (grid[cell].type() == BlockType::Vacant) {
+      grid.place_fruit(cell);
+    }
+  }
+  CHECK(!spawn_fruit(grid, snake, config, *fair));
+  CHECK(grid.free_count() == pocket.size());
+}
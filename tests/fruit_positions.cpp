@@ -0,0 +1,75 @@
+// The blocks `Grid::fruit_positions` finds fruit on, as fruit are spawned, eaten and moved about.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <algorithm>
+#include <vector>
+
+namespace {
+  using Positions = std::vector<std::size_t>;
+
+  // The same, found the slow way.
+  Positions scanned(const Grid &grid) {
+    Positions fruit;
+
+    for (std::size_t i = 0; i < grid.len(); i++) {
+      if (grid[i].type() == BlockType::OccupiedFruit) {
+        fruit.push_back(i);
+      }
+    }
+
+    return fruit;
+  }
+} // namespace
+
+int main() {
+  randomiser::seed(1);
+
+  Config config;
+  config.horizontal = 7;
+  config.vertical = 7;
+  config.keep_records = false;
+
+  Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+  Snake snake(grid, config);
+  lay_out(grid, snake, sf::Vector2u(0, 3));
+
+  CHECK(grid.fruit_positions().empty());
+
+  // Three laid out by hand along the snake's row and one off it, in index order whatever order
+  // they went in.
+  const auto at = [&](unsigned x, unsigned y) { return grid.to_index(sf::Vector2u(x, y)); };
+  for (const auto pos : {at(3, 3), at(1, 3), at(5, 0), at(2, 3)}) {
+    CHECK(grid.place_fruit(pos));
+  }
+  CHECK((grid.fruit_positions() == Positions{at(5, 0), at(1, 3), at(2, 3), at(3, 3)}));
+
+  // Eating the first two along the row takes them off.
+  snake.set_direction(Direction::Right);
+  CHECK(snake.move() == FruitKind::Normal);
+  CHECK(snake.move() == FruitKind::Normal);
+  CHECK((grid.fruit_positions() == Positions{at(5, 0), at(3, 3)}));
+
+  // A moved fruit is only on its new block, and still in index order.
+  grid.move_fruit(at(5, 0), at(6, 6));
+  CHECK((grid.fruit_positions() == Positions{at(3, 3), at(6, 6)}));
+
+  // Moved right in front of the head, and then eaten.
+  grid.move_fruit(at(3, 3), at(2, 2));
+  CHECK((grid.fruit_positions() == Positions{at(2, 2), at(6, 6)}));
+  snake.set_direction(Direction::Up);
+  CHECK(snake.move() == FruitKind::Normal);
+  CHECK((grid.fruit_positions() == Positions{at(6, 6)}));
+  CHECK(grid.fruit_count() == 1);
+
+  // And a few more wherever the spawner likes.
+  RandomSpawner spawner;
+  for (std::size_t i = 0; i < 3; i++) {
+    CHECK(spawn_fruit(grid, snake, config, spawner));
+  }
+
+  const auto fruit = grid.fruit_positions();
+  CHECK(fruit.size() == 4);
+  CHECK(fruit == scanned(grid));
+  CHECK(std::find(fruit.begin(), fruit.end(), at(6, 6)) != fruit.end());
+}
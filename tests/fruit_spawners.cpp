@@ -0,0 +1,90 @@
+// Where each of the fruit spawning strategies puts fruit.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <memory>
+
+int main() {
+  randomiser::seed(1);
+
+  Config config;
+  config.horizontal = 5;
+  config.vertical = 5;
+  config.spawn_mode = SpawnMode::Center;
+  config.keep_records = false;
+
+  Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+  Snake snake(grid, config);
+
+  const auto at = [&](unsigned x, unsigned y) { return grid.to_index(sf::Vector2u(x, y)); };
+
+  // A sequence skips what's off the grid or taken, then hands over to whatever comes after it.
+  {
+    SequenceSpawner sequence({sf::Vector2u(1, 1), sf::Vector2u(9, 9), sf::Vector2u(2, 2),
+                              sf::Vector2u(3, 3)},
+                             std::make_unique<RandomSpawner>());
+
+    CHECK(sequence.sequenced());
+    CHECK(sequence.pick(grid, snake) == at(1, 1));
+    // The head is on (2, 2).
+    CHECK(sequence.pick(grid, snake) == at(3, 3));
+    CHECK(sequence.position() == 4);
+    CHECK(sequence.sequenced());
+
+    const auto random = sequence.pick(grid, snake);
+    CHECK(random);
+    CHECK(!is_occupied(grid[*random].type()));
+    CHECK(!sequence.sequenced());
+    CHECK(sequence.position() == 5);
+
+    // Taken back to before it ran out.
+    sequence.set_position(2);
+    CHECK(sequence.sequenced());
+    CHECK(sequence.pick(grid, snake) == at(3, 3));
+  }
+
+  // Without anything after it, a finished sequence stays one and gives nothing more.
+  {
+    SequenceSpawner sequence({sf::Vector2u(1, 1)});
+
+    CHECK(sequence.pick(grid, snake) == at(1, 1));
+    CHECK(!sequence.pick(grid, snake));
+    CHECK(sequence.sequenced());
+  }
+
+  // Kept more than two blocks from the head, unless there's no other way.
+  {
+    RandomSpawner random;
+    ClearOfHeadSpawner clear(random, 2);
+
+    for (std::size_t i = 0; i < 100; i++) {
+      const auto pos = clear.pick(grid, snake);
+
+      CHECK(pos);
+      CHECK(grid.manhattan(*pos, at(2, 2)) > 2);
+    }
+
+    ClearOfHeadSpawner too_far(random, grid.len());
+    CHECK(too_far.pick(grid, snake));
+  }
+
+  // The config decides which one the game gets, and a sequence's fruit are all ordinary.
+  {
+    Config sequenced = config;
+    sequenced.fruit_sequence = {sf::Vector2u(0, 4)};
+    sequenced.chaos = true;
+
+    CHECK(!make_spawner(config)->sequenced());
+
+    const auto spawner = make_spawner(sequenced);
+    CHECK(spawner->sequenced());
+    CHECK(spawn_fruit(grid, snake, sequenced, *spawner));
+    CHECK(grid[at(0, 4)].type() == BlockType::OccupiedFruit);
+    CHECK(grid[at(0, 4)].fruit() == FruitKind::Normal);
+    CHECK(!spawn_fruit(grid, snake, sequenced, *spawner));
+
+    sequenced.fruit_sequence_random = true;
+    CHECK(spawn_fruit(grid, snake, sequenced, *make_spawner(sequenced)));
+    CHECK(grid.fruit_count() == 2);
+  }
+}
@@ -0,0 +1,55 @@
+// Spawning fruit on a board with nowhere, or only one block, left to put it.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <memory>
+#include <vector>
+
+int main() {
+  randomiser::seed(1);
+
+  Config config;
+  config.horizontal = 9;
+  config.vertical = 9;
+  config.keep_records = false;
+
+  Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+  Snake snake(grid, config);
+
+  const auto head = grid.to_index(snake.head());
+  const auto last = head == 0 ? grid.len() - 1 : 0;
+
+  for (std::size_t i = 0; i < grid.len(); i++) {
+    if (i != head && i != last) {
+      grid.set_type(i, BlockType::Wall);
+    }
+  }
+
+  std::vector<std::unique_ptr<FruitSpawner>> spawners;
+  spawners.push_back(std::make_unique<RandomSpawner>());
+  spawners.push_back(std::make_unique<ReachableSpawner>());
+  spawners.push_back(std::make_unique<ClearOfHeadSpawner>(*spawners.front(), 2));
+
+  // A single block left takes more than the few guesses there are before counting it off, and
+  // it's found every time.
+  for (std::size_t i = 0; i < 200; i++) {
+    CHECK(get_block_randomly(grid) == last);
+  }
+
+  CHECK(spawn_fruit(grid, snake, config, *spawners.front()));
+  CHECK(grid[last].type() == BlockType::OccupiedFruit);
+  CHECK(grid.free_count() == 0);
+
+  // With the board full, every kind of spawner gives up straight away and leaves it as it is.
+  for (auto &spawner : spawners) {
+    CHECK(!spawner->pick(grid, snake));
+    CHECK(!spawn_fruit(grid, snake, config, *spawner));
+  }
+
+  config.fair_spawn = true;
+  CHECK(!spawn_fruit(grid, snake, config, *make_spawner(config)));
+
+  CHECK(grid.fruit_count() == 1);
+  CHECK(grid.free_count() == 0);
+  CHECK_THROWS(get_block_randomly(grid), std::logic_error);
+}
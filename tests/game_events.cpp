@@ -0,0 +1,74 @@
+// What a game tells its observer, in order, over a game that goes exactly as laid out.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <vector>
+
+int main() {
+  randomiser::seed(1);
+
+  Config config;
+  config.horizontal = 7;
+  config.vertical = 5;
+  config.keep_records = false;
+  config.levels = true;
+  config.level_fruits = 2;
+
+  // Two fruit in a row and then the edge of the grid.
+  Scenario scenario;
+  scenario.horizontal = config.horizontal;
+  scenario.vertical = config.vertical;
+  scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+  scenario.blocks[2 + 2 * scenario.horizontal] = BlockType::OccupiedFruit;
+  scenario.blocks[3 + 2 * scenario.horizontal] = BlockType::OccupiedFruit;
+  scenario.head = sf::Vector2u(1, 2);
+  scenario.direction = Direction::Right;
+
+  Game game(config, sf::Vector2u(300, 300));
+
+  std::vector<GameEvent> events;
+  game.set_observer([&](const GameEvent &event) { events.push_back(event); });
+
+  CHECK(game.load_scenario(scenario));
+
+  for (std::size_t tick = 0; tick < 20 && !game.over(); tick++) {
+    game.advance(movement_interval);
+  }
+
+  using Kind = GameEvent::Kind;
+
+  struct Expected {
+    Kind kind;
+    sf::Vector2i pos;
+    std::size_t score;
+    std::size_t level;
+  };
+
+  const std::vector<Expected> expected = {
+    {Kind::Moved, {2, 2}, 0, 1},
+    {Kind::Ate, {2, 2}, 1, 1},
+    {Kind::Moved, {3, 2}, 1, 1},
+    {Kind::Ate, {3, 2}, 2, 1},
+    {Kind::LevelledUp, {3, 2}, 2, 2},
+    {Kind::Moved, {4, 2}, 2, 2},
+    {Kind::Moved, {5, 2}, 2, 2},
+    {Kind::Moved, {6, 2}, 2, 2},
+    // Where the fatal move would have taken the head.
+    {Kind::Ended, {7, 2}, 2, 2},
+  };
+
+  CHECK(events.size() == expected.size());
+
+  for (std::size_t i = 0; i < events.size() && i < expected.size(); i++) {
+    CHECK(events[i].kind == expected[i].kind);
+    CHECK(events[i].pos == expected[i].pos);
+    CHECK(events[i].score == expected[i].score);
+    CHECK(events[i].level == expected[i].level);
+
+    CHECK(events[i].fruit.has_value() == (events[i].kind == Kind::Ate));
+    CHECK(events[i].ending.has_value() == (events[i].kind == Kind::Ended));
+  }
+
+  CHECK(events[1].fruit == FruitKind::Normal);
+  CHECK(events.back().ending == Ending::OutOfBounds);
+}
@@ -0,0 +1,51 @@
+// Where the grid puts its blocks on screen, and how it finds them again.
+#include "check.hpp"
+#include "snek.hpp"
+
+int main() {
+  // Laid out from the origin a block at a time, row by row.
+  {
+    Grid grid(4, 3, grid_origin, sf::Vector2u(300, 300));
+
+    CHECK(grid.len() == 12);
+    CHECK(grid[0].position() == grid_origin);
+    CHECK(grid[grid.to_index(sf::Vector2u(3, 2))].position() ==
+          grid_origin + sf::Vector2f(3.0f * block_len, 2.0f * block_len));
+  }
+
+  // A point is in the block whose top left corner is at or before it, up to the far edges.
+  {
+    Grid grid(4, 3, grid_origin, sf::Vector2u(300, 300));
+    const auto at = [&](float x, float y) { return grid.cell_at_pixel(x, y); };
+
+    CHECK(at(grid_origin.x, grid_origin.y) == 0u);
+    CHECK(at(grid_origin.x + block_len - 0.01f, grid_origin.y) == 0u);
+    CHECK(at(grid_origin.x + block_len, grid_origin.y) == 1u);
+    CHECK(at(grid_origin.x, grid_origin.y + block_len) == 4u);
+
+    const auto far = grid_origin + sf::Vector2f(4.0f * block_len, 3.0f * block_len);
+    CHECK(at(far.x - 0.01f, far.y - 0.01f) == 11u);
+
+    for (std::size_t i = 0; i < grid.len(); i++) {
+      const auto middle = grid[i].position() + sf::Vector2f(block_len, block_len) / 2.0f;
+      CHECK(at(middle.x, middle.y) == i);
+    }
+
+    // Outside on every side.
+    CHECK(!at(grid_origin.x - 0.01f, grid_origin.y));
+    CHECK(!at(grid_origin.x, grid_origin.y - 0.01f));
+    CHECK(!at(far.x, grid_origin.y));
+    CHECK(!at(grid_origin.x, far.y));
+    CHECK(!at(-1000.0f, -1000.0f));
+  }
+
+  // A window that doesn't even reach past the origin has nowhere to put any of them.
+  {
+    Grid grid(4, 3, grid_origin, sf::Vector2u(1, 1));
+
+    CHECK(grid.len() == 12);
+    for (std::size_t i = 0; i < grid.len(); i++) {
+      CHECK(grid[i].position() == sf::Vector2f());
+    }
+  }
+}
@@ -0,0 +1,89 @@
+// Laying a grid out again at another size, and the setup screen's preview doing so.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <cstddef>
+
+namespace {
+  std::size_t count(const Grid &grid, BlockType type) {
+    std::size_t found = 0;
+
+    for (std::size_t i = 0; i < grid.len(); i++) {
+      found += grid[i].type() == type;
+    }
+
+    return found;
+  }
+} // namespace
+
+int main() {
+  const auto window = sf::Vector2u(600, 600);
+  const auto far_corner = sf::Vector2u(3, 2);
+  const auto near_corner = sf::Vector2u(1, 1);
+
+  Grid grid(4, 3, grid_origin, window);
+  grid.set_checkerboard(true);
+  grid.set_round_fruit(false);
+  grid.set_type(far_corner, BlockType::Wall);
+  grid.set_type(near_corner, BlockType::OccupiedFruit);
+  grid.set_fruit(grid.to_index(near_corner), FruitKind::Reverse);
+  grid.set_colour(near_corner, sf::Color::Magenta);
+
+  // Growing keeps every block where it was and leaves the new ones empty.
+  grid.resize(6, 5, grid_origin, window);
+  CHECK(grid.horizontal() == 6);
+  CHECK(grid.vertical() == 5);
+  CHECK(grid.len() == 30);
+  CHECK(grid.checkerboard());
+  CHECK(!grid.round_fruit());
+  CHECK(grid[far_corner].type() == BlockType::Wall);
+  CHECK(grid[near_corner].type() == BlockType::OccupiedFruit);
+  CHECK(grid[near_corner].fruit() == FruitKind::Reverse);
+  CHECK(grid[near_corner].colour() == sf::Color::Magenta);
+  CHECK(grid[sf::Vector2u(5, 4)].type() == BlockType::Vacant);
+  CHECK(count(grid, BlockType::Wall) == 1);
+  CHECK(grid[grid.to_index(sf::Vector2u(5, 4))].position() ==
+        grid_origin + sf::Vector2f(5.0f * block_len, 4.0f * block_len));
+
+  // Shrinking drops whatever was in the part cut off, and moves nothing that's left.
+  const auto moved = grid_origin + sf::Vector2f(block_len, 0.0f);
+  grid.resize(3, 2, moved, window);
+  CHECK(grid.len() == 6);
+  CHECK(count(grid, BlockType::Wall) == 0);
+  CHECK(grid[near_corner].fruit() == FruitKind::Reverse);
+  CHECK(grid[0].position() == moved);
+  CHECK(grid[grid.to_index(sf::Vector2u(2, 1))].position() ==
+        moved + sf::Vector2f(2.0f * block_len, block_len));
+
+  // The setup screen keeps its walls and its snake on the board as it's made smaller and larger.
+  Config config;
+  config.horizontal = 9;
+  config.vertical = 9;
+  config.setup = true;
+  config.keep_records = false;
+  config.walls = {sf::Vector2u(0, 0), sf::Vector2u(8, 8)};
+
+  Game game(config, window_size(config));
+  CHECK(game.state() == GameStates::Setup);
+
+  config.horizontal = 5;
+  config.vertical = 5;
+  CHECK(game.resize(config, window_size(config)));
+  CHECK(game.grid().len() == 25);
+  CHECK(count(game.grid(), BlockType::Wall) == 1);
+  CHECK(count(game.grid(), BlockType::OccupiedSnake) == 1);
+  CHECK(game.grid()[game.snake().head()].type() == BlockType::OccupiedSnake);
+
+  config.horizontal = 9;
+  config.vertical = 9;
+  CHECK(game.resize(config, window_size(config)));
+  CHECK(game.grid().len() == 81);
+  CHECK(game.grid()[sf::Vector2u(8, 8)].type() == BlockType::Wall);
+  CHECK(count(game.grid(), BlockType::Wall) == 2);
+  CHECK(count(game.grid(), BlockType::OccupiedSnake) == 1);
+
+  // Only the setup screen is laid out again like this.
+  config.setup = false;
+  Game started(config, window_size(config));
+  CHECK(!started.resize(config, window_size(config)));
+}
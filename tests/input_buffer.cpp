@@ -0,0 +1,79 @@
+// Turns pressed quicker than the snake moves, and how many of them `Config::input_buffer` keeps.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <initializer_list>
+#include <vector>
+
+namespace {
+  // Where the head is after each of `moves` moves, with all of `presses` pressed before the first,
+  // for a snake of three or, without a `body`, just the head.
+  std::vector<sf::Vector2u> play(std::size_t depth, std::initializer_list<Direction> presses,
+                                 std::size_t moves, bool body = true) {
+    Config config;
+    config.horizontal = 9;
+    config.vertical = 9;
+    config.keep_records = false;
+    config.input_buffer = depth;
+
+    Scenario scenario;
+    scenario.horizontal = config.horizontal;
+    scenario.vertical = config.vertical;
+    scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+    scenario.head = sf::Vector2u(4, 4);
+    if (body) {
+      scenario.body = {sf::Vector2u(3, 4), sf::Vector2u(2, 4)};
+    }
+    scenario.direction = Direction::Right;
+
+    Game game(config, sf::Vector2u(300, 300));
+    CHECK(game.load_scenario(scenario));
+
+    // Sets off.
+    game.advance(movement_interval);
+
+    for (const auto direct : presses) {
+      game.input(direct);
+    }
+
+    std::vector<sf::Vector2u> heads;
+    for (std::size_t i = 0; i < moves; i++) {
+      game.advance(movement_interval);
+      CHECK(!game.over());
+
+      heads.push_back(game.snake().head());
+    }
+
+    return heads;
+  }
+
+  using Path = std::vector<sf::Vector2u>;
+} // namespace
+
+int main() {
+  randomiser::seed(1);
+
+  const auto u_turn = {Direction::Up, Direction::Left, Direction::Down};
+
+  // The first turn is always taken on the next move; the buffer only holds the ones after it.
+  CHECK((play(0, u_turn, 3) == Path{{4, 3}, {4, 2}, {4, 1}}));
+  CHECK((play(1, u_turn, 3) == Path{{4, 3}, {3, 3}, {2, 3}}));
+  CHECK((play(2, u_turn, 3) == Path{{4, 3}, {3, 3}, {3, 4}}));
+
+  // A turn straight back from the one before it, or the same one again, never takes up room.
+  for (const std::size_t depth : {0, 1, 2}) {
+    CHECK((play(depth, {Direction::Up, Direction::Down, Direction::Up}, 2) ==
+           Path{{4, 3}, {4, 2}}));
+  }
+
+  // Not even with no body to turn back into, since the snake can't do that either.
+  CHECK((play(1, {Direction::Up, Direction::Down, Direction::Left}, 2, false) ==
+         Path{{4, 3}, {3, 3}}));
+
+  CHECK((play(1, {Direction::Up, Direction::Up, Direction::Left}, 2) == Path{{4, 3}, {3, 3}}));
+
+  // Nor does going the way the snake already is.
+  CHECK((play(1, {Direction::Right, Direction::Up}, 1) == Path{{4, 3}}));
+  // A reversal on its own is refused when it comes to be taken.
+  CHECK((play(2, {Direction::Left}, 2) == Path{{5, 4}, {6, 4}}));
+}
@@ -0,0 +1,52 @@
+// Eating past `Config::max_length`: the score keeps going up, the snake stops growing.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <vector>
+
+int main() {
+  randomiser::seed(1);
+
+  Config config;
+  config.horizontal = 9;
+  config.vertical = 5;
+  config.keep_records = false;
+  config.max_length = 3;
+
+  // Five fruit in a row, straight ahead.
+  Scenario scenario;
+  scenario.horizontal = config.horizontal;
+  scenario.vertical = config.vertical;
+  scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+  for (std::size_t x = 1; x <= 5; x++) {
+    scenario.blocks[x + 2 * scenario.horizontal] = BlockType::OccupiedFruit;
+  }
+  scenario.head = sf::Vector2u(0, 2);
+  scenario.direction = Direction::Right;
+
+  Game game(config, sf::Vector2u(300, 300));
+  CHECK(game.load_scenario(scenario));
+
+  // Sets off.
+  game.advance(movement_interval);
+
+  const std::size_t lengths[] = {2, 3, 3, 3, 3};
+  for (std::size_t i = 0; i < 5; i++) {
+    game.advance(movement_interval);
+
+    CHECK(!game.over());
+    CHECK(game.score() == i + 1);
+    CHECK(game.snake().len() == lengths[i]);
+  }
+
+  // Only the capped length is left on the board, right behind the head.
+  CHECK(game.snake().head() == sf::Vector2u(5, 2));
+  CHECK((game.snake().cells() ==
+         std::vector<sf::Vector2u>{sf::Vector2u(5, 2), sf::Vector2u(4, 2), sf::Vector2u(3, 2)}));
+
+  std::size_t marked = 0;
+  for (std::size_t i = 0; i < game.grid().len(); i++) {
+    marked += game.grid()[i].type() == BlockType::OccupiedSnake;
+  }
+  CHECK(marked == 3);
+}
@@ -0,0 +1,42 @@
+// How far along its move the snake is drawn, with and without time that hasn't been played yet.
+#include "check.hpp"
+#include "snek.hpp"
+
+int main() {
+  randomiser::seed(1);
+
+  Config config;
+  config.horizontal = 9;
+  config.vertical = 5;
+  config.keep_records = false;
+
+  Scenario scenario;
+  scenario.horizontal = config.horizontal;
+  scenario.vertical = config.vertical;
+  scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+  scenario.head = sf::Vector2u(0, 2);
+  scenario.direction = Direction::Right;
+
+  Game game(config, sf::Vector2u(300, 300));
+  CHECK(game.load_scenario(scenario));
+
+  // Sets off, and then makes the first move.
+  game.advance(movement_interval);
+  game.advance(movement_interval);
+  CHECK(game.snake().head() == sf::Vector2u(1, 2));
+  CHECK(game.move_progress() == 0.0f);
+
+  game.advance(movement_interval / 2.0f);
+  CHECK(game.move_progress() == 0.5f);
+  CHECK(game.move_progress(movement_interval / 4.0f) == 0.75f);
+  // Never past the next move.
+  CHECK(game.move_progress(movement_interval) == 1.0f);
+
+  // Drawing partway through a move, with the head sliding in from the block it left.
+  sf::RenderTexture texture;
+  game.render(texture, nullptr, movement_interval / 4.0f);
+
+  game.advance(movement_interval / 2.0f);
+  CHECK(game.snake().head() == sf::Vector2u(2, 2));
+  CHECK(game.move_progress() == 0.0f);
+}
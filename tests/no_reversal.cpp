@@ -0,0 +1,71 @@
+// However quickly turns come in, none of them take the head straight back into the neck.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <vector>
+
+namespace {
+  Scenario heading_right(const Config &config) {
+    Scenario scenario;
+    scenario.horizontal = config.horizontal;
+    scenario.vertical = config.vertical;
+    scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+    scenario.head = sf::Vector2u(config.horizontal / 2, config.vertical / 2);
+    scenario.body = {scenario.head - sf::Vector2u(1, 0), scenario.head - sf::Vector2u(2, 0)};
+    scenario.direction = Direction::Right;
+
+    return scenario;
+  }
+} // namespace
+
+int main() {
+  randomiser::seed(1);
+
+  Config config;
+  config.horizontal = 15;
+  config.vertical = 15;
+  config.keep_records = false;
+
+  for (const std::size_t depth : {0, 1, 2, 4}) {
+    config.input_buffer = depth;
+
+    // Up and straight back down before the next move: the snake goes up, and stays going up.
+    {
+      Game game(config, sf::Vector2u(300, 300));
+      const auto scenario = heading_right(config);
+      CHECK(game.load_scenario(scenario));
+
+      game.advance(movement_interval);
+      game.input(Direction::Up);
+      game.input(Direction::Down);
+
+      for (std::size_t i = 1; i <= 3; i++) {
+        game.advance(movement_interval);
+
+        CHECK(!game.over());
+        CHECK(game.snake().head() == scenario.head - sf::Vector2u(0, unsigned(i)));
+      }
+    }
+
+    // Mashing keys between every move, a few at random each time.
+    {
+      Game game(config, sf::Vector2u(300, 300));
+      CHECK(game.load_scenario(heading_right(config)));
+      game.advance(movement_interval);
+
+      const Direction keys[] = {Direction::Left, Direction::Right, Direction::Up, Direction::Down};
+
+      for (std::size_t tick = 0; tick < 300 && !game.over(); tick++) {
+        const auto neck = game.snake().body().front();
+
+        for (auto presses = randomiser::gen(0, 4); presses > 0; presses--) {
+          game.input(keys[randomiser::gen(0, 3)]);
+        }
+
+        game.advance(movement_interval);
+
+        CHECK(game.snake().head() != neck);
+      }
+    }
+  }
+}
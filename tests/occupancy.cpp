@@ -0,0 +1,61 @@
+// The grid's running counts of occupied and fruit blocks, against counting them afresh after every
+// move of whole games that put and take away fruit and walls in all sorts of ways.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <algorithm>
+#include <cstddef>
+
+namespace {
+  void check_counts(const Grid &grid) {
+    std::size_t occupied = 0;
+    std::size_t fruit = 0;
+
+    for (std::size_t i = 0; i < grid.len(); i++) {
+      occupied += is_occupied(grid[i].type());
+      fruit += grid[i].type() == BlockType::OccupiedFruit;
+    }
+
+    CHECK(grid.occupied_count() == occupied);
+    CHECK(grid.fruit_count() == fruit);
+    CHECK(grid.free_count() == grid.len() - occupied);
+  }
+} // namespace
+
+int main() {
+  static constexpr std::size_t games = 40;
+  static constexpr std::size_t max_ticks = 1000;
+
+  // So it's known the games got going at all.
+  std::size_t longest = 0;
+
+  for (std::size_t i = 0; i < games; i++) {
+    randomiser::seed(std::uint32_t(i));
+
+    Config config;
+    config.horizontal = 8;
+    config.vertical = 8;
+    config.keep_records = false;
+    config.initial_fruits = 1 + i % 3;
+    config.bonus_chance = 0.3f;
+    config.fruit_lifetime = 4.0f;
+    config.levels = i % 2 == 0;
+    config.level_fruits = 2;
+    config.magnet = i % 4 == 1;
+    config.paint = i % 4 == 3;
+    config.zen = i % 5 == 4;
+
+    // A demo, so the autopilot does the steering.
+    Game game(config, window_size(config), true);
+    check_counts(game.grid());
+
+    for (std::size_t tick = 0; tick < max_ticks && !game.over(); tick++) {
+      game.advance(movement_interval);
+      check_counts(game.grid());
+    }
+
+    longest = std::max(longest, game.snake().len());
+  }
+
+  CHECK(longest >= 10);
+}
@@ -0,0 +1,47 @@
+// Taking a move back in a practice game, and with it everything the move changed.
+#include "check.hpp"
+#include "snek.hpp"
+
+int main() {
+  randomiser::seed(1);
+
+  Config config;
+  config.horizontal = 7;
+  config.vertical = 7;
+  config.practice_undo = 3;
+  config.keep_records = false;
+
+  Scenario scenario;
+  scenario.horizontal = config.horizontal;
+  scenario.vertical = config.vertical;
+  scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+  scenario.blocks[2 + 3 * scenario.horizontal] = BlockType::OccupiedFruit;
+  scenario.head = sf::Vector2u(1, 3);
+  scenario.direction = Direction::Right;
+
+  Game game(config, sf::Vector2u(300, 300));
+  CHECK(game.load_scenario(scenario));
+  game.grid().set_fruit(game.grid().to_index(sf::Vector2u(2, 3)), FruitKind::Reverse);
+
+  for (std::size_t tick = 0; tick < 10 && game.step_count() == 0; tick++) {
+    game.advance(movement_interval);
+  }
+
+  CHECK(game.score() == 1);
+  CHECK(game.snake().len() == 2);
+
+  CHECK(game.undo());
+  CHECK(game.state() == GameStates::Paused);
+  CHECK(game.score() == 0);
+  CHECK(game.step_count() == 0);
+  CHECK(game.snake().head() == sf::Vector2u(1, 3));
+  CHECK(game.snake().len() == 1);
+  CHECK(game.grid()[sf::Vector2u(2, 3)].fruit() == FruitKind::Reverse);
+
+  // The reverse fruit was never eaten, so the controls aren't reversed.
+  game.toggle_pause();
+  game.input(Direction::Up);
+  CHECK(game.snake().direction() == Direction::Up);
+
+  CHECK(!game.undo());
+}
@@ -0,0 +1,93 @@
+// Closing the window on a run that's still going, or paused, ends it like any other and saves it.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <filesystem>
+#include <optional>
+#include <string>
+
+namespace fs = std::filesystem;
+
+int main() {
+  randomiser::seed(1);
+
+  // Kept out of the way of anyone's real records.
+  const auto dir = fs::temp_directory_path() / "snek-quit-saves";
+  fs::remove_all(dir);
+
+  for (const bool paused : {false, true}) {
+    fs::create_directories(dir);
+
+    Config config;
+    config.horizontal = 9;
+    config.vertical = 5;
+    config.records_dir = dir.string();
+
+    Scenario scenario;
+    scenario.horizontal = config.horizontal;
+    scenario.vertical = config.vertical;
+    scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+    scenario.blocks[2 + 2 * scenario.horizontal] = BlockType::OccupiedFruit;
+    scenario.head = sf::Vector2u(0, 2);
+    scenario.direction = Direction::Right;
+
+    Game game(config, sf::Vector2u(300, 300));
+    CHECK(game.load_scenario(scenario));
+
+    std::optional<Ending> ending;
+    std::size_t ended = 0;
+    game.set_observer([&](const GameEvent &event) {
+      if (event.kind == GameEvent::Kind::Ended) {
+        ending = event.ending;
+        ended++;
+      }
+    });
+
+    // Sets off and eats on the second move.
+    for (std::size_t tick = 0; tick < 3; tick++) {
+      game.advance(movement_interval);
+    }
+    CHECK(game.score() == 1);
+
+    if (paused) {
+      game.toggle_pause();
+      CHECK(game.state() == GameStates::Paused);
+    } else {
+      CHECK(game.state() == GameStates::InProgress);
+    }
+
+    game.quit();
+    CHECK(game.over());
+    CHECK(ending == Ending::Quit);
+    CHECK(game.window_title().find("(score: 1, steps: 2, length: 2)") != std::string::npos);
+
+    // Only the once, however many times it's asked.
+    game.quit();
+    CHECK(ended == 1);
+
+    const auto stats = load_stats((dir / stats_path).string());
+    CHECK(stats.games == 1);
+    CHECK(stats.fruit == 1);
+    CHECK(stats.best_length == 2);
+    // Quitting isn't a death.
+    CHECK(stats.collisions + stats.out_of_bounds + stats.walls == 0);
+    CHECK(fs::exists(dir / ghost_path));
+
+    fs::remove_all(dir);
+  }
+
+  // Nothing to save before the run has started.
+  {
+    fs::create_directories(dir);
+
+    Config config;
+    config.records_dir = dir.string();
+
+    Game game(config, sf::Vector2u(300, 300));
+    game.quit();
+    CHECK(!game.over());
+    CHECK(!fs::exists(dir / stats_path));
+
+    fs::remove_all(dir);
+  }
+}
@@ -0,0 +1,71 @@
+// The flood fill the autopilot and fair spawns share, with and without wrapping round the edges.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <algorithm>
+#include <cstddef>
+#include <vector>
+
+namespace {
+  std::size_t count(const std::vector<bool> &seen) {
+    return std::size_t(std::count(seen.begin(), seen.end(), true));
+  }
+} // namespace
+
+int main() {
+  Grid grid(6, 5, grid_origin, sf::Vector2u(300, 300));
+  const auto corner = grid.to_index(sf::Vector2u(0, 0));
+  const auto walls = std::vector<BlockType>{BlockType::Wall};
+
+  // On an open board everything is, from anywhere.
+  CHECK(count(reachable(grid, corner, walls)) == grid.len());
+  CHECK(count(reachable(grid, grid.to_index(sf::Vector2u(3, 2)), walls, true)) == grid.len());
+
+  // Neighbours off the edge only exist when wrapping.
+  CHECK(!grid.neighbour(corner, Direction::Left, false));
+  CHECK(grid.neighbour(corner, Direction::Left, true) == grid.to_index(sf::Vector2u(5, 0)));
+  CHECK(grid.neighbour(corner, Direction::Up, true) == grid.to_index(sf::Vector2u(0, 4)));
+  CHECK(grid.neighbour(corner, Direction::Right, false) == grid.to_index(sf::Vector2u(1, 0)));
+
+  // A wall all the way down column 2 splits the board in two, unless the edges wrap round.
+  for (unsigned y = 0; y < grid.vertical(); y++) {
+    grid.set_type(sf::Vector2u(2, y), BlockType::Wall);
+  }
+
+  const auto left = reachable(grid, corner, walls);
+  CHECK(count(left) == 2 * grid.vertical());
+  CHECK(left[grid.to_index(sf::Vector2u(1, 4))]);
+  CHECK(!left[grid.to_index(sf::Vector2u(3, 0))]);
+  CHECK(!left[grid.to_index(sf::Vector2u(2, 0))]);
+
+  const auto wrapped = reachable(grid, corner, walls, true);
+  CHECK(count(wrapped) == grid.len() - grid.vertical());
+  CHECK(wrapped[grid.to_index(sf::Vector2u(5, 2))]);
+
+  // A pocket walled in on every side, wrap or not.
+  const auto pocket = grid.to_index(sf::Vector2u(4, 2));
+  for (const auto pos : {sf::Vector2u(4, 1), sf::Vector2u(4, 3), sf::Vector2u(3, 2),
+                         sf::Vector2u(5, 2)}) {
+    grid.set_type(pos, BlockType::Wall);
+  }
+
+  CHECK(count(reachable(grid, pocket, walls, true)) == 1);
+  CHECK(!reachable(grid, corner, walls, true)[pocket]);
+
+  // The start counts even when it's of a blocked type, like the head of a snake.
+  grid.set_type(corner, BlockType::OccupiedSnake);
+  grid.set_type(sf::Vector2u(0, 1), BlockType::OccupiedSnake);
+  const auto body = std::vector<BlockType>{BlockType::Wall, BlockType::OccupiedSnake};
+
+  const auto around = reachable(grid, corner, body);
+  CHECK(around[corner]);
+  CHECK(!around[grid.to_index(sf::Vector2u(0, 1))]);
+  CHECK(count(around) == 2 * grid.vertical() - 1);
+
+  // Anything can be in the way for `reachable_if`: here everything below the top row, as well as
+  // the wall across it.
+  const auto top_row = reachable_if(grid, grid.to_index(sf::Vector2u(5, 0)), [&](std::size_t pos) {
+    return grid.to_xy(pos).y > 0 || grid[pos].type() == BlockType::Wall;
+  });
+  CHECK(count(top_row) == 3);
+}
@@ -0,0 +1,79 @@
+// A game taking a restart from the very frame it's over on, whichever way it ended.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <optional>
+#include <vector>
+
+namespace {
+  Scenario empty(std::size_t horizontal, std::size_t vertical, sf::Vector2u head,
+                 Direction direct) {
+    Scenario scenario;
+    scenario.horizontal = horizontal;
+    scenario.vertical = vertical;
+    scenario.blocks.assign(horizontal * vertical, BlockType::Vacant);
+    scenario.head = head;
+    scenario.direction = direct;
+
+    return scenario;
+  }
+
+  // Plays `scenario` until it's over, checking that a restart is refused up to the advance that
+  // ends it and taken from then on.
+  Ending play_out(const Scenario &scenario) {
+    Config config;
+    config.horizontal = scenario.horizontal;
+    config.vertical = scenario.vertical;
+    config.keep_records = false;
+
+    Game game(config, sf::Vector2u(300, 300));
+    CHECK(game.load_scenario(scenario));
+
+    std::optional<Ending> ending;
+    game.set_observer([&](const GameEvent &event) {
+      if (event.kind == GameEvent::Kind::Ended) {
+        ending = event.ending;
+      }
+    });
+
+    for (std::size_t tick = 0; tick < 20 && !game.over(); tick++) {
+      CHECK(!game.can_restart());
+
+      game.advance(movement_interval);
+
+      CHECK(game.can_restart() == game.over());
+    }
+
+    CHECK(game.over());
+    CHECK(ending);
+
+    // And it stays that way.
+    game.advance(movement_interval);
+    CHECK(game.can_restart());
+
+    return *ending;
+  }
+} // namespace
+
+int main() {
+  randomiser::seed(1);
+
+  CHECK(play_out(empty(5, 5, sf::Vector2u(2, 2), Direction::Right)) == Ending::OutOfBounds);
+
+  // Eats the only two blocks there are besides its own.
+  auto filled = empty(3, 1, sf::Vector2u(0, 0), Direction::Right);
+  filled.blocks = {BlockType::Vacant, BlockType::OccupiedFruit, BlockType::OccupiedFruit};
+  CHECK(play_out(filled) == Ending::Won);
+
+  // Every segment behind the head on the same block, which ends the game as broken on the first
+  // move in any build.
+  auto broken = empty(5, 5, sf::Vector2u(2, 2), Direction::Up);
+  broken.body = {sf::Vector2u(2, 3), sf::Vector2u(2, 3), sf::Vector2u(2, 3)};
+  CHECK(play_out(broken) == Ending::Error);
+
+  // Nor is anything taken before the game has even started.
+  Config config;
+  config.keep_records = false;
+  Game game(config, sf::Vector2u(300, 300));
+  CHECK(!game.can_restart());
+}
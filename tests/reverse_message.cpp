@@ -0,0 +1,50 @@
+// The title only stops saying the controls are reversed if that's still what it says when they
+// go back to normal.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <string>
+
+int main() {
+  randomiser::seed(1);
+
+  for (const bool said_since : {false, true}) {
+    Config config;
+    config.horizontal = 30;
+    config.vertical = 3;
+    config.keep_records = false;
+    // Nothing else turns up on the way.
+    config.spawn_interval = 1000.0f;
+
+    Scenario scenario;
+    scenario.horizontal = config.horizontal;
+    scenario.vertical = config.vertical;
+    scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+    scenario.blocks[1 + scenario.horizontal] = BlockType::OccupiedFruit;
+    scenario.head = sf::Vector2u(0, 1);
+    scenario.direction = Direction::Right;
+
+    Game game(config, sf::Vector2u(800, 300));
+    CHECK(game.load_scenario(scenario));
+    game.grid().set_fruit(game.grid().to_index(sf::Vector2u(1, 1)), FruitKind::Reverse);
+
+    // Sets off and eats on the first move.
+    game.advance(movement_interval);
+    game.advance(movement_interval);
+    CHECK(game.score() == 1);
+    CHECK(game.window_title().find(reverse_message) != std::string::npos);
+
+    if (said_since) {
+      game.set_message("something else");
+    }
+
+    for (float seconds = 0.0f; seconds < reverse_duration + movement_interval;
+         seconds += movement_interval) {
+      game.advance(movement_interval);
+    }
+
+    CHECK(!game.over());
+    CHECK(game.window_title().find(reverse_message) == std::string::npos);
+    CHECK((game.window_title().find("something else") != std::string::npos) == said_since);
+  }
+}
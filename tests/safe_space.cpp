@@ -0,0 +1,27 @@
+// The room a move leaves the snake, which goes by the way it last moved rather than the way it has
+// been turned since.
+#include "check.hpp"
+#include "snek.hpp"
+
+int main() {
+  Config config;
+  config.horizontal = 6;
+  config.vertical = 6;
+  config.keep_records = false;
+  // So the neck, being the tail too, would be fair game if only the tail counted.
+  config.tail_safe = true;
+
+  Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+  Snake snake(grid, config);
+  // Moved right, and has just been turned up.
+  lay_out(grid, snake, sf::Vector2u(2, 2), {sf::Vector2u(1, 2)}, Direction::Up);
+  CHECK(snake.heading() == Direction::Right);
+
+  // Back into the neck.
+  CHECK(safe_space(snake, grid, Direction::Left) == 0);
+
+  // Everything but the block the head leaves, the tail moving off its own.
+  for (const auto direct : {Direction::Right, Direction::Up, Direction::Down}) {
+    CHECK(safe_space(snake, grid, direct) == grid.len() - 1);
+  }
+}
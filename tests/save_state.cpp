@@ -0,0 +1,101 @@
+// Game states written out by `to_json` and read back in by `load_json`, and the broken snakes the
+// latter turns away.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <stdexcept>
+#include <string>
+#include <vector>
+
+namespace {
+  const sf::Vector2u resolution(300, 300);
+} // namespace
+
+int main() {
+  randomiser::seed(1);
+
+  Config config;
+  config.horizontal = 6;
+  config.vertical = 5;
+  config.keep_records = false;
+
+  Grid grid(config.horizontal, config.vertical, config.origin, resolution);
+  Snake snake(grid, config);
+
+  lay_out(grid, snake, sf::Vector2u(2, 1), {sf::Vector2u(2, 2), sf::Vector2u(3, 2)}, Direction::Up);
+  grid.place_fruit(grid.to_index(sf::Vector2u(5, 4)));
+  grid.set_fruit(grid.to_index(sf::Vector2u(5, 4)), FruitKind::Bonus);
+  grid.set_type(grid.to_index(sf::Vector2u(0, 0)), BlockType::Wall);
+  grid.set_colour(grid.to_index(sf::Vector2u(0, 0)), sf::Color(1, 2, 3, 4));
+
+  const auto saved = to_json(snake, grid, 7);
+
+  // Into a grid of another size, which the saved one replaces.
+  Config other = config;
+  other.horizontal = 3;
+  other.vertical = 3;
+
+  Grid loaded_grid(other.horizontal, other.vertical, other.origin, resolution);
+  Snake loaded_snake(loaded_grid, other);
+
+  CHECK(load_json(saved, loaded_grid, loaded_snake, resolution) == 7);
+
+  CHECK(loaded_grid.horizontal() == grid.horizontal());
+  CHECK(loaded_grid.vertical() == grid.vertical());
+  for (std::size_t i = 0; i < grid.len(); i++) {
+    CHECK(loaded_grid[i].type() == grid[i].type());
+    CHECK(loaded_grid[i].fruit() == grid[i].fruit());
+    CHECK(loaded_grid[i].colour() == grid[i].colour());
+  }
+
+  CHECK(loaded_snake.head() == snake.head());
+  CHECK(loaded_snake.body() == snake.body());
+  CHECK(loaded_snake.direction() == snake.direction());
+
+  // Written out again, nothing has changed.
+  CHECK(to_json(loaded_snake, loaded_grid, 7) == saved);
+
+  const auto rejected = [&](const std::string &text) {
+    const auto before = to_json(loaded_snake, loaded_grid, 7);
+
+    CHECK_THROWS(load_json(text, loaded_grid, loaded_snake, resolution), std::runtime_error);
+    CHECK(to_json(loaded_snake, loaded_grid, 7) == before);
+  };
+
+  // A gap between the head and the body.
+  lay_out(grid, snake, sf::Vector2u(2, 1), {sf::Vector2u(2, 3), sf::Vector2u(3, 3)}, Direction::Up);
+  rejected(to_json(snake, grid, 0));
+
+  // Two segments on one block.
+  lay_out(grid, snake, sf::Vector2u(2, 1), {sf::Vector2u(2, 2), sf::Vector2u(2, 2)}, Direction::Up);
+  rejected(to_json(snake, grid, 0));
+
+  // A block marked as snake that no segment is on.
+  lay_out(grid, snake, sf::Vector2u(2, 1), {sf::Vector2u(2, 2)}, Direction::Up);
+  grid.set_type(grid.to_index(sf::Vector2u(4, 4)), BlockType::OccupiedSnake);
+  rejected(to_json(snake, grid, 0));
+
+  // Corner to corner only holds together with diagonal moves.
+  lay_out(grid, snake, sf::Vector2u(2, 1), {sf::Vector2u(3, 2)}, Direction::Up);
+  rejected(to_json(snake, grid, 0));
+
+  Config diagonal = other;
+  diagonal.diagonal = true;
+
+  Grid diagonal_grid(diagonal.horizontal, diagonal.vertical, diagonal.origin, resolution);
+  Snake diagonal_snake(diagonal_grid, diagonal);
+
+  CHECK(load_json(to_json(snake, grid, 0), diagonal_grid, diagonal_snake, resolution) == 0);
+  CHECK(diagonal_snake.body().front() == sf::Vector2u(3, 2));
+
+  rejected("{\"horizontal\": 6}");
+
+  // Scores that aren't sizes.
+  for (const auto *score : {"-1", "2.5", "1e30"}) {
+    auto text = saved;
+    const std::string field = "\"score\": 7";
+
+    text.replace(text.find(field), field.size(), std::string("\"score\": ") + score);
+    rejected(text);
+  }
+}
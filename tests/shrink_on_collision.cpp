@@ -0,0 +1,55 @@
+// Running into its own body with `Config::shrink_on_collision` cuts the snake short there instead
+// of ending the game.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <vector>
+
+namespace {
+  // Head at (3, 3) facing right, into the third segment of a body that curls round under and
+  // to the right of it.
+  void curl_up(Grid &grid, Snake &snake) {
+    lay_out(grid, snake, sf::Vector2u(3, 3),
+            {sf::Vector2u(3, 4), sf::Vector2u(4, 4), sf::Vector2u(4, 3), sf::Vector2u(4, 2),
+             sf::Vector2u(5, 2)},
+            Direction::Right);
+  }
+} // namespace
+
+int main() {
+  Config config;
+  config.horizontal = 7;
+  config.vertical = 7;
+  config.keep_records = false;
+
+  {
+    Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+    Snake snake(grid, config);
+    curl_up(grid, snake);
+
+    CHECK_THROWS(snake.move(), CollisionException);
+  }
+
+  config.shrink_on_collision = true;
+
+  {
+    Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+    Snake snake(grid, config);
+    curl_up(grid, snake);
+
+    CHECK(!snake.move());
+
+    // The segment that was hit and the two behind it are gone, and the tail moved on as usual.
+    CHECK(snake.len() == 3);
+    CHECK(snake.head() == sf::Vector2u(4, 3));
+    CHECK((snake.cells() ==
+           std::vector<sf::Vector2u>{sf::Vector2u(4, 3), sf::Vector2u(3, 3), sf::Vector2u(3, 4)}));
+
+    for (const auto pos : {sf::Vector2u(4, 4), sf::Vector2u(4, 2), sf::Vector2u(5, 2)}) {
+      CHECK(grid[pos].type() == BlockType::Vacant);
+    }
+
+    CHECK(grid.free_count() == grid.len() - 3);
+    snake.validate();
+  }
+}
@@ -0,0 +1,62 @@
+// The snake starting out somewhere it can move away from, however boxed in the rest of the board
+// is.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <vector>
+
+namespace {
+  // Walls everywhere but the blocks given.
+  Grid walled(const Config &config, const std::vector<sf::Vector2u> &open) {
+    Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+
+    for (std::size_t i = 0; i < grid.len(); i++) {
+      grid.set_type(i, BlockType::Wall);
+    }
+    for (const auto pos : open) {
+      grid.set_type(pos, BlockType::Vacant);
+    }
+
+    return grid;
+  }
+} // namespace
+
+int main() {
+  Config config;
+  config.horizontal = 7;
+  config.vertical = 7;
+  config.keep_records = false;
+
+  // Every open block but two is walled in on all four sides, the middle one included.
+  const std::vector<sf::Vector2u> open = {
+    sf::Vector2u(1, 1), sf::Vector2u(3, 1), sf::Vector2u(5, 1), sf::Vector2u(1, 3),
+    sf::Vector2u(3, 3), sf::Vector2u(1, 5), sf::Vector2u(3, 5), sf::Vector2u(5, 3),
+    sf::Vector2u(5, 4)};
+
+  for (const auto mode : {SpawnMode::Random, SpawnMode::Center}) {
+    config.spawn_mode = mode;
+
+    for (std::uint32_t seed = 0; seed < 200; seed++) {
+      randomiser::seed(seed);
+
+      auto grid = walled(config, open);
+      Snake snake(grid, config);
+
+      CHECK(snake.head() == sf::Vector2u(5, 3) || snake.head() == sf::Vector2u(5, 4));
+      CHECK(snake.has_room(snake.head()));
+    }
+  }
+
+  // With nowhere that has any room, any open block will do.
+  {
+    auto grid = walled(config, {sf::Vector2u(2, 2)});
+    Snake snake(grid, config);
+
+    CHECK(snake.head() == sf::Vector2u(2, 2));
+  }
+
+  {
+    auto grid = walled(config, {});
+    CHECK_THROWS(Snake(grid, config), std::runtime_error);
+  }
+}
@@ -0,0 +1,84 @@
+// The movement interval along `Config::speed_curve`: starting slow and closing in on the fastest
+// it goes.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <cmath>
+#include <vector>
+
+namespace {
+  bool close(float a, float b) { return std::abs(a - b) < 1e-5f; }
+} // namespace
+
+int main() {
+  Config config;
+  config.speed_curve = true;
+
+  // The defaults, to begin with.
+  CHECK(close(curve_interval(0, config), config.curve_start));
+  CHECK(close(curve_interval(1, config),
+              config.curve_min + (config.curve_start - config.curve_min) * 0.85f));
+
+  // Faster with every fruit, and never past the minimum however many there are.
+  for (std::size_t eaten = 1; eaten < 200; eaten++) {
+    CHECK(curve_interval(eaten, config) < curve_interval(eaten - 1, config) ||
+          close(curve_interval(eaten, config), config.curve_min));
+    CHECK(curve_interval(eaten, config) >= config.curve_min);
+  }
+
+  CHECK(close(curve_interval(1000, config), config.curve_min));
+
+  // Half way there after as many fruit as halve what's left.
+  config.curve_start = 1.0f;
+  config.curve_min = 0.2f;
+  config.curve_rate = 0.5f;
+  CHECK(close(curve_interval(0, config), 1.0f));
+  CHECK(close(curve_interval(1, config), 0.6f));
+  CHECK(close(curve_interval(3, config), 0.3f));
+  CHECK(close(curve_interval(100, config), 0.2f));
+
+  // A game moves that far apart, fruit by fruit: three in a row and then none.
+  config.horizontal = 9;
+  config.vertical = 5;
+  config.keep_records = false;
+
+  Scenario scenario;
+  scenario.horizontal = config.horizontal;
+  scenario.vertical = config.vertical;
+  scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+  for (std::size_t x = 1; x <= 3; x++) {
+    scenario.blocks[x + 2 * scenario.horizontal] = BlockType::OccupiedFruit;
+  }
+  scenario.head = sf::Vector2u(0, 2);
+  scenario.direction = Direction::Right;
+
+  Game game(config, window_size(config));
+  CHECK(game.load_scenario(scenario));
+
+  static constexpr float tick = 0.01f;
+  float now = 0.0f;
+  std::vector<float> moved_at;
+  game.set_observer([&](const GameEvent &event) {
+    if (event.kind == GameEvent::Kind::Moved) {
+      moved_at.push_back(now);
+    }
+  });
+
+  // Sets off.
+  game.advance(tick);
+
+  while (moved_at.size() < 6 && now < 10.0f) {
+    now += tick;
+    game.advance(tick);
+  }
+
+  CHECK(moved_at.size() == 6);
+
+  // The first move comes after the starting interval, and eats.
+  CHECK(std::abs(moved_at.front() - 1.0f) < 2.0f * tick);
+
+  const float gaps[] = {0.6f, 0.4f, 0.3f, 0.3f, 0.3f};
+  for (std::size_t i = 0; i < 5; i++) {
+    CHECK(std::abs(moved_at[i + 1] - moved_at[i] - gaps[i]) < 2.0f * tick);
+  }
+}
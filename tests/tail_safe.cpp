@@ -0,0 +1,74 @@
+// Moving into the block the tail is just leaving, which only `Config::tail_safe` allows, and only
+// while the tail actually leaves it.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <vector>
+
+int main() {
+  Config config;
+  config.horizontal = 6;
+  config.vertical = 6;
+  config.keep_records = false;
+
+  // Round a two by two square, the head right behind the tail.
+  const auto head = sf::Vector2u(1, 1);
+  const std::vector<sf::Vector2u> square = {sf::Vector2u(1, 2), sf::Vector2u(2, 2),
+                                            sf::Vector2u(2, 1)};
+
+  for (const bool safe : {true, false}) {
+    config.tail_safe = safe;
+
+    Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+    Snake snake(grid, config);
+    lay_out(grid, snake, head, square, Direction::Right);
+
+    if (!safe) {
+      CHECK_THROWS(snake.move(), CollisionException);
+      continue;
+    }
+
+    // Chasing its tail round and round.
+    for (const auto direct : {Direction::Right, Direction::Down, Direction::Left, Direction::Up,
+                              Direction::Right, Direction::Down}) {
+      snake.set_direction(direct);
+      CHECK(!snake.move());
+      CHECK(snake.len() == 4);
+      snake.validate();
+    }
+
+    CHECK(grid.occupied_count() == 4);
+  }
+
+  config.tail_safe = true;
+
+  // Growing leaves the tail where it was on the move that eats, and the move after that can go
+  // into it again as it moves off once more.
+  {
+    Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+    Snake snake(grid, config);
+    lay_out(grid, snake, head, {sf::Vector2u(1, 2), sf::Vector2u(2, 2)}, Direction::Right);
+    CHECK(grid.place_fruit(grid.to_index(sf::Vector2u(2, 1))));
+
+    CHECK(snake.move() == FruitKind::Normal);
+    CHECK(snake.len() == 4);
+    CHECK(grid[sf::Vector2u(2, 2)].type() == BlockType::OccupiedSnake);
+
+    snake.set_direction(Direction::Down);
+    CHECK(!snake.move());
+    CHECK(snake.head() == sf::Vector2u(2, 2));
+    CHECK(snake.len() == 4);
+    snake.validate();
+  }
+
+  // Only the very tip of the tail moves out of the way; the segment ahead of it doesn't.
+  {
+    Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+    Snake snake(grid, config);
+    lay_out(grid, snake, head,
+            {sf::Vector2u(1, 2), sf::Vector2u(2, 2), sf::Vector2u(2, 1), sf::Vector2u(3, 1)},
+            Direction::Right);
+
+    CHECK_THROWS(snake.move(), CollisionException);
+  }
+}
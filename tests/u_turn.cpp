@@ -0,0 +1,77 @@
+// A snake just long enough to curl round into the block its tail is leaving, and the same turn
+// when it grew on the way round and the tail stayed put.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <optional>
+
+namespace {
+  struct Played {
+    std::optional<Ending> ending;
+    sf::Vector2i last;
+    std::size_t len;
+  };
+
+  Played curl(bool grow) {
+    Config config;
+    config.horizontal = 6;
+    config.vertical = 5;
+    config.keep_records = false;
+
+    Scenario scenario;
+    scenario.horizontal = config.horizontal;
+    scenario.vertical = config.vertical;
+    scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+    scenario.head = sf::Vector2u(3, 1);
+    scenario.body = {sf::Vector2u(2, 1), sf::Vector2u(1, 1), sf::Vector2u(0, 1)};
+    scenario.direction = Direction::Down;
+
+    if (grow) {
+      scenario.blocks[2 + 2 * scenario.horizontal] = BlockType::OccupiedFruit;
+    }
+
+    Game game(config, sf::Vector2u(300, 300));
+    CHECK(game.load_scenario(scenario));
+
+    Played played{};
+    game.set_observer([&](const GameEvent &event) {
+      played.last = event.pos;
+
+      if (event.kind == GameEvent::Kind::Ended) {
+        played.ending = event.ending;
+      }
+    });
+
+    // Down to set off, then left and back up into where the tail was.
+    game.advance(movement_interval);
+    game.advance(movement_interval);
+
+    for (const auto direct : {Direction::Left, Direction::Up, Direction::Right}) {
+      game.input(direct);
+      game.advance(movement_interval);
+
+      if (game.over())
+        break;
+    }
+
+    played.len = game.snake().len();
+    return played;
+  }
+} // namespace
+
+int main() {
+  randomiser::seed(1);
+
+  // Round and round with the tail a block ahead.
+  const auto chased = curl(false);
+  CHECK(!chased.ending);
+  CHECK(chased.last == sf::Vector2i(3, 1));
+  CHECK(chased.len == 4);
+
+  // Eating on the way round keeps the tail where it was, so the block the head goes back up into
+  // is still body.
+  const auto grown = curl(true);
+  CHECK(grown.ending == Ending::Collision);
+  CHECK(grown.last == sf::Vector2i(2, 1));
+  CHECK(grown.len == 5);
+}
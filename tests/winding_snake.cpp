@@ -0,0 +1,115 @@
+// A snake that fills its grid by winding back and forth across it, checked whole after every move,
+// and games that end instead of throwing once their snake has come apart.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <optional>
+#include <vector>
+
+int main() {
+  randomiser::seed(1);
+
+  Config config;
+  config.horizontal = 5;
+  config.vertical = 4;
+  config.keep_records = false;
+
+  {
+    Grid grid(config.horizontal, config.vertical, config.origin, sf::Vector2u(300, 300));
+    Snake snake(grid, config);
+    lay_out(grid, snake, sf::Vector2u(0, 0));
+
+    // Along the top row, back along the next one and so on, with a fruit on every block of the
+    // way.
+    std::vector<Direction> path;
+    for (std::size_t row = 0; row < config.vertical; row++) {
+      const auto across = row % 2 == 0 ? Direction::Right : Direction::Left;
+
+      for (std::size_t i = 1; i < config.horizontal; i++) {
+        path.push_back(across);
+      }
+
+      if (row + 1 < config.vertical) {
+        path.push_back(Direction::Down);
+      }
+    }
+
+    for (std::size_t i = 1; i < grid.len(); i++) {
+      CHECK(grid.place_fruit(i));
+    }
+
+    for (std::size_t i = 0; i < path.size(); i++) {
+      snake.set_direction(path[i]);
+
+      CHECK(snake.move() == FruitKind::Normal);
+      CHECK(snake.len() == i + 2);
+
+      snake.validate();
+    }
+
+    CHECK(snake.len() == grid.len());
+    CHECK(grid.free_count() == 0);
+    CHECK(snake.head() == sf::Vector2u(0, 3));
+  }
+
+#ifndef NDEBUG
+  // Only debug builds check the whole body after every move. The last segment starts out on the
+  // same block as the first, and leaving it takes the mark off the block the middle one moves to.
+  {
+    Scenario scenario;
+    scenario.horizontal = config.horizontal;
+    scenario.vertical = config.vertical;
+    scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+    scenario.head = sf::Vector2u(2, 2);
+    scenario.body = {sf::Vector2u(2, 3), sf::Vector2u(3, 3), sf::Vector2u(2, 3)};
+    scenario.direction = Direction::Up;
+
+    Game game(config, sf::Vector2u(300, 300));
+    CHECK(game.load_scenario(scenario));
+
+    std::optional<Ending> ending;
+    game.set_observer([&](const GameEvent &event) {
+      if (event.kind == GameEvent::Kind::Ended) {
+        ending = event.ending;
+      }
+    });
+
+    for (std::size_t tick = 0; tick < 10 && !game.over(); tick++) {
+      game.advance(movement_interval);
+    }
+
+    CHECK(game.state() == GameStates::End);
+    CHECK(ending == Ending::Error);
+  }
+#endif
+
+  // Every build catches two segments on one block. All three behind the head start out on the
+  // same one, and after a move the last two still are.
+  {
+    Scenario scenario;
+    scenario.horizontal = config.horizontal;
+    scenario.vertical = config.vertical;
+    scenario.blocks.assign(scenario.horizontal * scenario.vertical, BlockType::Vacant);
+    scenario.head = sf::Vector2u(2, 2);
+    scenario.body = {sf::Vector2u(2, 3), sf::Vector2u(2, 3), sf::Vector2u(2, 3)};
+    scenario.direction = Direction::Up;
+
+    Game game(config, sf::Vector2u(300, 300));
+    CHECK(game.load_scenario(scenario));
+
+    std::optional<Ending> ending;
+    game.set_observer([&](const GameEvent &event) {
+      if (event.kind == GameEvent::Kind::Ended) {
+        ending = event.ending;
+      }
+    });
+
+    for (std::size_t tick = 0; tick < 10 && !game.over(); tick++) {
+      game.advance(movement_interval);
+    }
+
+    CHECK(game.state() == GameStates::End);
+    CHECK(ending == Ending::Error);
+    CHECK(game.snake().head() == sf::Vector2u(2, 1));
+  }
+}
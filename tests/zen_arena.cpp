@@ -0,0 +1,42 @@
+// Zen mode's arena opening up ring by ring, and leaving alone the walls it didn't put there.
+#include "check.hpp"
+#include "snek.hpp"
+
+#include <cstddef>
+
+namespace {
+  std::size_t walls(const Grid &grid) {
+    std::size_t count = 0;
+
+    for (std::size_t i = 0; i < grid.len(); i++) {
+      count += grid[i].type() == BlockType::Wall;
+    }
+
+    return count;
+  }
+} // namespace
+
+int main() {
+  Grid grid(9, 9, grid_origin, sf::Vector2u(300, 300));
+
+  const auto own = grid.to_index(sf::Vector2u(0, 4));
+  grid.set_type(own, BlockType::Wall);
+
+  // Three rings of walls leave a 3 by 3 arena in the middle.
+  Arena arena(grid, 3);
+  CHECK(walls(grid) == 81 - 9);
+  CHECK(grid[sf::Vector2u(4, 4)].type() == BlockType::Vacant);
+  CHECK(grid[sf::Vector2u(2, 2)].type() == BlockType::Wall);
+
+  CHECK(arena.expand());
+  CHECK(walls(grid) == 81 - 25);
+  CHECK(grid[sf::Vector2u(2, 2)].type() == BlockType::Vacant);
+
+  CHECK(arena.expand());
+  CHECK(arena.expand());
+  CHECK(!arena.expand());
+
+  // The outermost ring is open again, bar the wall that was already there.
+  CHECK(walls(grid) == 1);
+  CHECK(grid[own].type() == BlockType::Wall);
+}